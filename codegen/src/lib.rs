@@ -0,0 +1,200 @@
+//! Generates the response models in `src/models.rs` from the OpenAPI
+//! document checked in under `openapi/`.
+//!
+//! The document is maintained by hand rather than copied from slurmrestd,
+//! and there is one for all API versions. slurmrestd generates the specs
+//! of `v0.0.39` and later at runtime, and every version's responses are
+//! normalized into the `v0.0.38` shape anyway, so per-version models would
+//! all end up as the same types. The document describes the `slurm` plugin
+//! schemas of `v0.0.38` with the few fields later versions added. The
+//! `slurmdb` models are not generated and are written by hand in
+//! `src/lib.rs`.
+//!
+//! Every schema in `components.schemas` becomes a struct named after the
+//! schema without its version prefix, e.g. `v0.0.38_partition` becomes
+//! `Partition`. Fields follow the conventions of the hand-written types:
+//! scalars and references are `Option`s and arrays are `Vec`s, all with
+//! `#[serde(default)]`, so a field slurmrestd leaves out never fails to
+//! deserialize.
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// The OpenAPI document the models are generated from, relative to the
+/// workspace root.
+pub const SPEC: &str = "openapi/models.json";
+
+/// The generated module, relative to the workspace root.
+pub const MODELS: &str = "src/models.rs";
+
+/// Schemas shared with the `slurmdb` plugin, which are written by hand in
+/// `src/lib.rs` and only referenced from the generated code.
+const EXTERNAL: [(&str, &str); 2] = [("meta", "Meta"), ("error", "Error")];
+
+/// Generate the models module for an OpenAPI document. `source` is the
+/// path of the document, recorded in the header of the module.
+pub fn generate(document: &Value, source: &str) -> Result<String, String> {
+    let schemas = document
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .ok_or("document has no components.schemas")?;
+
+    let mut out = String::new();
+    writeln!(out, "//! Response models generated from `{source}`.").unwrap();
+    writeln!(out, "//!").unwrap();
+    writeln!(
+        out,
+        "//! @generated by `cargo run -p slurm-rs-codegen`, do not edit by hand."
+    )
+    .unwrap();
+    writeln!(out, "use crate::{{Error, Meta}};").unwrap();
+    writeln!(out, "use schemars::JsonSchema;").unwrap();
+    writeln!(out, "use serde::{{Deserialize, Serialize}};").unwrap();
+
+    for (name, schema) in schemas {
+        if external(name).is_some() {
+            continue;
+        }
+        out.push('\n');
+        write_struct(&mut out, name, schema)?;
+    }
+    Ok(out)
+}
+
+fn write_struct(out: &mut String, name: &str, schema: &Value) -> Result<(), String> {
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .ok_or_else(|| format!("{name}: only object schemas are supported"))?;
+
+    if let Some(description) = schema.get("description").and_then(Value::as_str) {
+        write_doc(out, "", description);
+    }
+    let anchor = name.replace('.', "_");
+    writeln!(
+        out,
+        "/// SEE: <https://slurm.schedmd.com/rest_api.html#{anchor}>"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]"
+    )
+    .unwrap();
+    writeln!(out, "pub struct {} {{", type_name(name)).unwrap();
+    write_fields(out, name, properties)?;
+    writeln!(out, "}}").unwrap();
+    Ok(())
+}
+
+fn write_fields(
+    out: &mut String,
+    name: &str,
+    properties: &Map<String, Value>,
+) -> Result<(), String> {
+    for (property, schema) in properties {
+        let field = field_name(property);
+        let inner = rust_type(schema).map_err(|e| format!("{name}.{property}: {e}"))?;
+        // Arrays default to empty and the envelope's `meta` is always
+        // sent, so neither is wrapped in an `Option`.
+        let is_array = schema.get("type").and_then(Value::as_str) == Some("array");
+        let rust_type = if is_array || inner == "Meta" {
+            inner
+        } else {
+            format!("Option<{inner}>")
+        };
+
+        if let Some(description) = schema.get("description").and_then(Value::as_str) {
+            write_doc(out, "    ", description);
+        }
+        if &field == property {
+            writeln!(out, "    #[serde(default)]").unwrap();
+        } else {
+            writeln!(out, "    #[serde(default, rename = \"{property}\")]").unwrap();
+        }
+        writeln!(out, "    pub {field}: {rust_type},").unwrap();
+    }
+    Ok(())
+}
+
+// The Rust type of a schema, without the `Option` fields are wrapped in.
+fn rust_type(schema: &Value) -> Result<String, String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference
+            .strip_prefix("#/components/schemas/")
+            .ok_or_else(|| format!("unsupported reference {reference}"))?;
+        return Ok(type_name(name));
+    }
+    let format = schema.get("format").and_then(Value::as_str);
+    match schema.get("type").and_then(Value::as_str) {
+        Some("string") => Ok("String".into()),
+        Some("integer") if format == Some("int32") => Ok("i32".into()),
+        Some("integer") => Ok("i64".into()),
+        Some("number") => Ok("f64".into()),
+        Some("boolean") => Ok("bool".into()),
+        Some("array") => {
+            let items = schema.get("items").ok_or("array without items")?;
+            Ok(format!("Vec<{}>", rust_type(items)?))
+        }
+        Some(other) => Err(format!("unsupported type {other}")),
+        None => Err("schema without a type".into()),
+    }
+}
+
+// `v0.0.38_job_resources` -> `JobResources`, `v0.0.38_meta` -> `Meta`.
+fn type_name(schema: &str) -> String {
+    if let Some(external) = external(schema) {
+        return external.to_string();
+    }
+    let name = schema.split_once('_').map_or(schema, |(_, name)| name);
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn external(schema: &str) -> Option<&'static str> {
+    let name = schema.split_once('_').map_or(schema, |(_, name)| name);
+    EXTERNAL
+        .iter()
+        .find(|(external, _)| *external == name)
+        .map(|(_, rust)| *rust)
+}
+
+// `LicenseName` -> `license_name`, `RPCs` -> `rpcs`.
+fn field_name(property: &str) -> String {
+    let mut field = String::new();
+    let mut previous_lower = false;
+    for c in property.chars() {
+        if c.is_ascii_uppercase() && previous_lower {
+            field.push('_');
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        field.push(c.to_ascii_lowercase());
+    }
+    if field == "type" {
+        field.insert_str(0, "r#");
+    }
+    field
+}
+
+fn write_doc(out: &mut String, indent: &str, description: &str) {
+    let mut line = String::new();
+    for word in description.split_whitespace() {
+        if !line.is_empty() && indent.len() + 4 + line.len() + 1 + word.len() > 76 {
+            writeln!(out, "{indent}/// {line}").unwrap();
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        writeln!(out, "{indent}/// {line}").unwrap();
+    }
+}
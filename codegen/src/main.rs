@@ -0,0 +1,24 @@
+//! Regenerate `src/models.rs` from `openapi/models.json`. Run from
+//! anywhere in the workspace with `cargo run -p slurm-rs-codegen`.
+use std::{fs, path::Path, process};
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let spec = fs::read_to_string(root.join(slurm_rs_codegen::SPEC)).unwrap_or_else(|e| {
+        eprintln!("reading {}: {e}", slurm_rs_codegen::SPEC);
+        process::exit(1);
+    });
+    let document = serde_json::from_str(&spec).unwrap_or_else(|e| {
+        eprintln!("parsing {}: {e}", slurm_rs_codegen::SPEC);
+        process::exit(1);
+    });
+    let models =
+        slurm_rs_codegen::generate(&document, slurm_rs_codegen::SPEC).unwrap_or_else(|e| {
+            eprintln!("generating models: {e}");
+            process::exit(1);
+        });
+    if let Err(e) = fs::write(root.join(slurm_rs_codegen::MODELS), models) {
+        eprintln!("writing {}: {e}", slurm_rs_codegen::MODELS);
+        process::exit(1);
+    }
+}
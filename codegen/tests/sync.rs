@@ -0,0 +1,16 @@
+use std::{fs, path::Path};
+
+#[test]
+fn models_match_spec() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let spec = fs::read_to_string(root.join(slurm_rs_codegen::SPEC)).unwrap();
+    let document = serde_json::from_str(&spec).unwrap();
+    let expected = slurm_rs_codegen::generate(&document, slurm_rs_codegen::SPEC).unwrap();
+    let actual = fs::read_to_string(root.join(slurm_rs_codegen::MODELS)).unwrap();
+    assert!(
+        actual == expected,
+        "{} is out of date with {}, run `cargo run -p slurm-rs-codegen`",
+        slurm_rs_codegen::MODELS,
+        slurm_rs_codegen::SPEC
+    );
+}
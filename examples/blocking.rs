@@ -0,0 +1,11 @@
+use anyhow::Result;
+use slurm_rs::blocking::Slurm;
+
+fn main() -> Result<()> {
+    let slurm = Slurm::new_from_env();
+    println!("ping slurm without an async runtime");
+
+    println!("{}", serde_json::to_string_pretty(&slurm.ping()?).unwrap());
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use slurm_rs::{JobUpdate, Slurm};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let slurm = Slurm::new_from_env();
+    println!("hold a job and extend its time limit");
+    slurm
+        .update_job("42", &JobUpdate::new().hold().time_limit(120))
+        .await?;
+
+    println!("release it again");
+    slurm.update_job("42", &JobUpdate::new().release()).await?;
+
+    println!("send it SIGUSR1");
+    slurm.cancel_job("42", Some("SIGUSR1")).await?;
+
+    println!("cancel it");
+    slurm.cancel_job("42", None).await?;
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use slurm_rs::Slurm;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let slurm = Slurm::builder().load_env().connect().await?;
+    println!("slurmrestd offers {}", slurm.api_version());
+
+    let ping = slurm.ping().await?;
+    println!(
+        "responses come from {}",
+        ping.meta.api_version().unwrap_or(slurm.api_version())
+    );
+
+    Ok(())
+}
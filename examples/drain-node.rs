@@ -0,0 +1,16 @@
+use anyhow::Result;
+use slurm_rs::{NodeUpdate, Slurm};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let slurm = Slurm::new_from_env();
+    println!("drain node");
+    slurm
+        .update_node("node001", &NodeUpdate::drain("bad dimm"))
+        .await?;
+
+    println!("resume node");
+    slurm.update_node("node001", &NodeUpdate::resume()).await?;
+
+    Ok(())
+}
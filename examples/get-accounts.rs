@@ -0,0 +1,21 @@
+use anyhow::Result;
+use slurm_rs::SlurmDB;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let slurmdb = SlurmDB::new_from_env();
+    println!("get slurmdb accounts");
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurmdb.get_accounts().await?).unwrap()
+    );
+
+    println!("get 'root' slurmdb account");
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurmdb.get_account("root").await?).unwrap()
+    );
+
+    Ok(())
+}
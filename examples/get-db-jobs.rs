@@ -0,0 +1,24 @@
+use anyhow::Result;
+use slurm_rs::{DbJobsQuery, SlurmDB};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let slurmdb = SlurmDB::new_from_env();
+    println!("get failed slurmdb jobs since yesterday");
+
+    let query = DbJobsQuery::new()
+        .start_time("now-1days")
+        .states(["FAILED", "TIMEOUT"]);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurmdb.get_jobs(&query).await?).unwrap()
+    );
+
+    println!("get a specific jobs accounting info");
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurmdb.get_job("42").await?).unwrap()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use slurm_rs::{Jwt, Slurm};
+use std::{env, time::Duration};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let key = env::var("X_SLURM_JWT_KEY")?;
+    let user = env::var("X_SLURM_USER_NAME")?;
+    let jwt = Jwt::from_key_file(key, user)?.lifespan(Duration::from_secs(600));
+
+    let slurm = Slurm::builder().load_env().credentials(jwt).build()?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurm.ping().await?).unwrap()
+    );
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use anyhow::Result;
 use slurm_rs::Slurm;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let slurm = Slurm::new_from_env();
+    let slurm = Slurm::builder()
+        .load_env()
+        .timeout(Duration::from_secs(30))
+        .user_agent("slurm-rs/ping-slurm")
+        .build()?;
     println!("ping, slurm!");
 
     println!(
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use slurm_rs::{CommandCredentials, Slurm};
+use std::{env, time::Duration};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Runs `scontrol token lifespan=600` again whenever the token is about
+    // to expire or slurmrestd rejects it.
+    let user = env::var("X_SLURM_USER_NAME")?;
+    let credentials = CommandCredentials::scontrol(user, Duration::from_secs(600));
+
+    let slurm = Slurm::builder()
+        .load_env()
+        .credentials(credentials)
+        .build()?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurm.ping().await?).unwrap()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use slurm_rs::SlurmClient;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = SlurmClient::builder().load_env().build()?;
+    let slurm = client.slurm();
+    let slurmdb = client.slurmdb();
+
+    println!("get slurm diag");
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurm.get_diag().await?).unwrap()
+    );
+
+    println!("get slurmdb diag over the same connection");
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurmdb.get_diag().await?).unwrap()
+    );
+
+    Ok(())
+}
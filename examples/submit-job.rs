@@ -0,0 +1,27 @@
+use anyhow::Result;
+use slurm_rs::{JobProperties, JobSubmission, Slurm};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let slurm = Slurm::new_from_env();
+    println!("submit a slurm job");
+
+    let job = JobProperties::new()
+        .name("slurm-rs-example")
+        .partition("debug")
+        .time_limit(5)
+        .tasks(1)
+        .cpus_per_task(1)
+        .memory_per_node(1024)
+        .current_working_directory("/tmp")
+        .standard_output("/tmp/slurm-rs-example.out")
+        .env("PATH", "/bin:/usr/bin");
+    let submission = JobSubmission::new("#!/bin/bash\nhostname\n", job);
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurm.submit_job(&submission).await?).unwrap()
+    );
+
+    Ok(())
+}
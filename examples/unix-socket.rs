@@ -0,0 +1,15 @@
+use anyhow::Result;
+use slurm_rs::Slurm;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let path = env::var("X_SLURM_SOCKET").unwrap_or_else(|_| "/run/slurmrestd.sock".into());
+    let slurm = Slurm::unix(path)?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&slurm.ping().await?).unwrap()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+//! Billing jobs by the weights partitions set in `TRESBillingWeights`.
+use crate::{Partition, Result, SlurmError, Tres, TresKey};
+use std::{fmt, str::FromStr};
+
+/// How weighted TRES counts add up to a job's billing, chosen by
+/// `PriorityFlags` in `slurm.conf`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BillingMode {
+    /// The sum of every weighted count, Slurm's default.
+    #[default]
+    Sum,
+    /// `PriorityFlags=MAX_TRES`: the largest weighted count of CPUs,
+    /// memory, nodes and generic resources, plus the sum of the others,
+    /// like licenses.
+    MaxTres,
+}
+
+/// The weight of each TRES in a job's billing, parsed from a partition's
+/// `billing_weights`, like `CPU=1.0,Mem=0.25G,GRES/gpu=2.0`.
+///
+/// A weight may have a unit suffix to weigh a size per unit: `Mem=0.25G`
+/// bills 0.25 per gigabyte. Weights are kept per megabyte, the unit `Tres`
+/// counts sizes in. TRES without a weight are not billed.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BillingWeights {
+    weights: Vec<(TresKey, f64)>,
+}
+
+impl BillingWeights {
+    /// The weight of one unit of a TRES, or zero if it has none. Types and
+    /// names are compared ignoring case, as slurmctld does.
+    pub fn weight(&self, key: &TresKey) -> f64 {
+        self.weights
+            .iter()
+            .find(|(k, _)| same(k, key))
+            .map_or(0.0, |(_, w)| *w)
+    }
+
+    /// Whether no weights are set.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// The billable TRES of a job using `tres`, computed the way slurmctld
+    /// computes `billable_tres`. Pass the job's allocated TRES for the
+    /// value slurmctld reports, or its requested TRES for an estimate
+    /// before submitting.
+    ///
+    /// Without weights, as on partitions that set no `TRESBillingWeights`,
+    /// slurmctld bills the number of CPUs.
+    pub fn billable(&self, tres: &Tres, mode: BillingMode) -> f64 {
+        if self.is_empty() {
+            return tres.cpu() as f64;
+        }
+        let mut per_node: f64 = 0.0;
+        let mut global = 0.0;
+        for (key, count) in tres.iter() {
+            if key.kind() == "billing" {
+                continue;
+            }
+            let billed = self.weight(key) * count as f64;
+            let node_tres = matches!(key.kind(), "cpu" | "mem" | "node" | "gres");
+            if mode == BillingMode::MaxTres && node_tres {
+                per_node = per_node.max(billed);
+            } else {
+                global += billed;
+            }
+        }
+        per_node + global
+    }
+}
+
+impl FromStr for BillingWeights {
+    type Err = SlurmError;
+
+    /// Parse billing weights. An empty string sets no weights.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |reason: &str| SlurmError::Parse(format!("billing weights '{s}': {reason}"));
+
+        let mut weights = Vec::new();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, weight) = pair.split_once('=').ok_or_else(|| error("missing '='"))?;
+            let key: TresKey = key.parse().map_err(|_| error("missing type"))?;
+            let weight = parse_weight(&key, weight).ok_or_else(|| error("invalid weight"))?;
+            weights.push((key, weight));
+        }
+        Ok(BillingWeights { weights })
+    }
+}
+
+impl fmt::Display for BillingWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, weight)) in self.weights.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{key}={weight}")?;
+        }
+        Ok(())
+    }
+}
+
+// A weight with an optional unit suffix, as the weight of one unit Slurm
+// counts the TRES in: megabytes for sizes, single items for the rest.
+fn parse_weight(key: &TresKey, weight: &str) -> Option<f64> {
+    const UNITS: [char; 5] = ['K', 'M', 'G', 'T', 'P'];
+    // How many steps of 1024 the unit counted in is above single items.
+    let base = if key.is_size() { 2 } else { 0 };
+
+    let (number, unit) = match weight.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&weight[..i], Some(c.to_ascii_uppercase())),
+        _ => (weight, None),
+    };
+    let weight: f64 = number.parse().ok().filter(|w: &f64| w.is_finite())?;
+    match unit {
+        None => Some(weight),
+        Some(unit) => {
+            let unit = UNITS.iter().position(|u| *u == unit)? + 1;
+            let steps = unit.checked_sub(base)? as i32;
+            Some(weight / 1024f64.powi(steps))
+        }
+    }
+}
+
+fn same(a: &TresKey, b: &TresKey) -> bool {
+    let name = |k: &TresKey| k.name().map(str::to_lowercase);
+    a.kind() == b.kind() && name(a) == name(b)
+}
+
+impl Partition {
+    /// The partition's `TRESBillingWeights`.
+    pub fn parsed_billing_weights(&self) -> Result<BillingWeights> {
+        self.billing_weights.as_deref().unwrap_or_default().parse()
+    }
+
+    /// The billable TRES of a job using `tres` in this partition, see
+    /// `BillingWeights::billable`.
+    pub fn billable_tres(&self, tres: &Tres, mode: BillingMode) -> Result<f64> {
+        Ok(self.parsed_billing_weights()?.billable(tres, mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: &str = "CPU=1.0,Mem=0.25G,GRES/gpu=2.0";
+
+    fn weight(weights: &str, key: &str) -> f64 {
+        let weights: BillingWeights = weights.parse().unwrap();
+        weights.weight(&key.parse().unwrap())
+    }
+
+    #[test]
+    fn size_weights_are_per_megabyte() {
+        assert_eq!(weight(WEIGHTS, "mem"), 0.25 / 1024.0);
+        assert_eq!(weight("Mem=3", "mem"), 3.0);
+        assert_eq!(weight("Mem=3M", "mem"), 3.0);
+        assert_eq!(weight("mem=2g", "mem"), 2.0 / 1024.0);
+        assert_eq!(weight("Mem=1T", "mem"), 1.0 / (1024.0 * 1024.0));
+    }
+
+    #[test]
+    fn keys_compare_ignoring_case() {
+        assert_eq!(weight(WEIGHTS, "cpu"), 1.0);
+        assert_eq!(weight(WEIGHTS, "gres/GPU"), 2.0);
+        assert_eq!(weight(WEIGHTS, "node"), 0.0);
+    }
+
+    #[test]
+    fn max_tres_takes_the_largest_node_tres() {
+        let weights: BillingWeights = WEIGHTS.parse().unwrap();
+        // 4 CPUs, 32G billed as 8, 2 GPUs billed as 4.
+        let tres: Tres = "cpu=4,mem=32G,gres/gpu=2,billing=99".parse().unwrap();
+        assert_eq!(weights.billable(&tres, BillingMode::Sum), 16.0);
+        assert_eq!(weights.billable(&tres, BillingMode::MaxTres), 8.0);
+    }
+
+    #[test]
+    fn max_tres_still_sums_licenses() {
+        let weights: BillingWeights = "CPU=1,License/matlab=3".parse().unwrap();
+        let tres: Tres = "cpu=4,license/matlab=1".parse().unwrap();
+        assert_eq!(weights.billable(&tres, BillingMode::MaxTres), 7.0);
+    }
+
+    #[test]
+    fn without_weights_cpus_are_billed() {
+        let weights = BillingWeights::default();
+        let tres: Tres = "cpu=6,mem=1G".parse().unwrap();
+        assert_eq!(weights.billable(&tres, BillingMode::Sum), 6.0);
+        assert_eq!(weights.billable(&tres, BillingMode::MaxTres), 6.0);
+    }
+
+    #[test]
+    fn parse_and_write() {
+        let weights: BillingWeights = WEIGHTS.parse().unwrap();
+        assert_eq!(weights.to_string(), "cpu=1,mem=0.000244140625,gres/gpu=2");
+        assert_eq!(
+            weights.to_string().parse::<BillingWeights>().unwrap(),
+            weights
+        );
+        assert!("".parse::<BillingWeights>().unwrap().is_empty());
+        for s in ["CPU", "CPU=", "=1", "CPU=x", "CPU=inf", "Mem=1Q"] {
+            assert!(s.parse::<BillingWeights>().is_err(), "{s}");
+        }
+    }
+}
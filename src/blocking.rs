@@ -0,0 +1,353 @@
+//! Synchronous clients for callers without an async runtime, like small
+//! command line tools and build scripts.
+//!
+//! Each client drives the async one on its own single-threaded tokio
+//! runtime, so the methods must not be called from within async code; doing
+//! so panics.
+use crate::{
+    AccountsResponse, ApiVersion, AssociationsResponse, ClustersResponse, Conditional, DbConfig,
+    DbDiag, DbJobsQuery, DbJobsResponse, Diag, ErrorPolicy, JobSubmission, JobSubmitResponse,
+    JobUpdate, JobsResponse, Licenses, NodeUpdate, NodesResponse, PartitionsResponse, Pings,
+    QosResponse, ReservationsResponse, Result, SlurmBuilder, SlurmError, TresResponse,
+    UsersResponse, WckeysResponse,
+};
+use std::{future::Future, sync::Arc};
+use tokio::runtime::Runtime;
+
+/// Blocking version of `slurm_rs::Slurm`, with the same methods.
+#[derive(Clone)]
+pub struct Slurm {
+    inner: crate::Slurm,
+    runtime: Arc<Runtime>,
+}
+
+impl Slurm {
+    /// Start building a new blocking Slurm client. Unlike `new` and
+    /// `new_from_env`, the builder reports problems as errors instead of
+    /// panicking.
+    pub fn builder() -> SlurmBuilder<Slurm> {
+        SlurmBuilder::new()
+    }
+
+    /// Create a new blocking Slurm client, see `slurm_rs::Slurm::new`.
+    /// This will panic if creating a client fails.
+    pub fn new<U, T, L>(user: U, token: T, url: L) -> Self
+    where
+        U: ToString,
+        T: ToString,
+        L: ToString,
+    {
+        Slurm::builder()
+            .user(user)
+            .token(token)
+            .endpoint(url)
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
+    }
+
+    /// Create a new blocking Slurm client from environment variables.
+    /// This will panic if a variable is missing or creating a client fails.
+    pub fn new_from_env() -> Self {
+        Slurm::builder()
+            .load_env()
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
+    }
+
+    /// Create a client for a local slurmrestd listening on a unix socket
+    /// with `auth/local`, see `slurm_rs::Slurm::unix`.
+    #[cfg(unix)]
+    pub fn unix<P: Into<std::path::PathBuf>>(path: P) -> Result<Self> {
+        Slurm::builder().unix_socket(path).local_auth().build()
+    }
+
+    /// Make blocking calls through an existing async client.
+    pub fn from_async(slurm: crate::Slurm) -> Result<Self> {
+        Ok(Slurm::with_runtime(slurm, runtime()?))
+    }
+
+    pub(crate) fn with_runtime(slurm: crate::Slurm, runtime: Runtime) -> Self {
+        Slurm {
+            inner: slurm,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client requests are sent with.
+    pub fn as_async(&self) -> &crate::Slurm {
+        &self.inner
+    }
+
+    /// Set how errors Slurm reports in an otherwise successful response
+    /// are handled. Defaults to `ErrorPolicy::Strict`.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.inner = self.inner.with_error_policy(error_policy);
+        self
+    }
+
+    /// Version of the REST API requests are sent to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.inner.api_version()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Ping test!
+    pub fn ping(&self) -> Result<Pings> {
+        self.block_on(self.inner.ping())
+    }
+
+    /// Get all parition information
+    pub fn get_partitions(&self) -> Result<PartitionsResponse> {
+        self.block_on(self.inner.get_partitions())
+    }
+
+    /// Get all partition information, but only if any of it changed since
+    /// `update_time`, in seconds since the epoch.
+    pub fn get_partitions_since(
+        &self,
+        update_time: i64,
+    ) -> Result<Conditional<PartitionsResponse>> {
+        self.block_on(self.inner.get_partitions_since(update_time))
+    }
+
+    /// Get a specific parition's information
+    pub fn get_partition(&self, partition: &str) -> Result<PartitionsResponse> {
+        self.block_on(self.inner.get_partition(partition))
+    }
+
+    /// Get all nodes information
+    pub fn get_nodes(&self) -> Result<NodesResponse> {
+        self.block_on(self.inner.get_nodes())
+    }
+
+    /// Get all nodes information, but only if any of it changed since
+    /// `update_time`, in seconds since the epoch.
+    pub fn get_nodes_since(&self, update_time: i64) -> Result<Conditional<NodesResponse>> {
+        self.block_on(self.inner.get_nodes_since(update_time))
+    }
+
+    /// Get a specific node's information
+    pub fn get_node(&self, node: &str) -> Result<NodesResponse> {
+        self.block_on(self.inner.get_node(node))
+    }
+
+    /// Update a node, see `slurm_rs::Slurm::update_node`.
+    pub fn update_node(&self, node: &str, update: &NodeUpdate) -> Result<()> {
+        self.block_on(self.inner.update_node(node, update))
+    }
+
+    /// Delete a dynamic node.
+    pub fn delete_node(&self, node: &str) -> Result<()> {
+        self.block_on(self.inner.delete_node(node))
+    }
+
+    /// Get diagnostics information
+    pub fn get_diag(&self) -> Result<Diag> {
+        self.block_on(self.inner.get_diag())
+    }
+
+    /// Get all reservations
+    pub fn get_reservations(&self) -> Result<ReservationsResponse> {
+        self.block_on(self.inner.get_reservations())
+    }
+
+    /// Get a specific reservation
+    pub fn get_reservation(&self, reservation: &str) -> Result<ReservationsResponse> {
+        self.block_on(self.inner.get_reservation(reservation))
+    }
+
+    /// Get all jobs
+    pub fn get_jobs(&self) -> Result<JobsResponse> {
+        self.block_on(self.inner.get_jobs())
+    }
+
+    /// Get all jobs, but only if any of them changed since `update_time`,
+    /// in seconds since the epoch.
+    pub fn get_jobs_since(&self, update_time: i64) -> Result<Conditional<JobsResponse>> {
+        self.block_on(self.inner.get_jobs_since(update_time))
+    }
+
+    /// Get a specific job
+    pub fn get_job(&self, job: &str) -> Result<JobsResponse> {
+        self.block_on(self.inner.get_job(job))
+    }
+
+    /// Cancel a job, or send it a signal. Without a signal slurmctld
+    /// sends `SIGKILL`.
+    pub fn cancel_job(&self, job: &str, signal: Option<&str>) -> Result<()> {
+        self.block_on(self.inner.cancel_job(job, signal))
+    }
+
+    /// Update a pending or running job.
+    pub fn update_job(&self, job: &str, update: &JobUpdate) -> Result<()> {
+        self.block_on(self.inner.update_job(job, update))
+    }
+
+    /// Get licenses
+    pub fn get_licenses(&self) -> Result<Licenses> {
+        self.block_on(self.inner.get_licenses())
+    }
+
+    /// Submit a new job
+    pub fn submit_job(&self, job: &JobSubmission) -> Result<JobSubmitResponse> {
+        self.block_on(self.inner.submit_job(job))
+    }
+}
+
+/// Blocking version of `slurm_rs::SlurmDB`, with the same methods.
+#[derive(Clone)]
+pub struct SlurmDB {
+    inner: crate::SlurmDB,
+    runtime: Arc<Runtime>,
+}
+
+impl SlurmDB {
+    /// Start building a new blocking SlurmDB client. Unlike `new` and
+    /// `new_from_env`, the builder reports problems as errors instead of
+    /// panicking.
+    pub fn builder() -> SlurmBuilder<SlurmDB> {
+        SlurmBuilder::new()
+    }
+
+    /// Create a new blocking SlurmDB client, see `slurm_rs::SlurmDB::new`.
+    /// This will panic if creating a client fails.
+    pub fn new<U, T, L>(user: U, token: T, url: L) -> Self
+    where
+        U: ToString,
+        T: ToString,
+        L: ToString,
+    {
+        SlurmDB::builder()
+            .user(user)
+            .token(token)
+            .endpoint(url)
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
+    }
+
+    /// Create a new blocking SlurmDB client from environment variables.
+    /// This will panic if a variable is missing or creating a client fails.
+    pub fn new_from_env() -> Self {
+        SlurmDB::builder()
+            .load_env()
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
+    }
+
+    /// Create a client for a local slurmrestd listening on a unix socket
+    /// with `auth/local`, see `slurm_rs::SlurmDB::unix`.
+    #[cfg(unix)]
+    pub fn unix<P: Into<std::path::PathBuf>>(path: P) -> Result<Self> {
+        SlurmDB::builder().unix_socket(path).local_auth().build()
+    }
+
+    /// Make blocking calls through an existing async client.
+    pub fn from_async(slurmdb: crate::SlurmDB) -> Result<Self> {
+        Ok(SlurmDB::with_runtime(slurmdb, runtime()?))
+    }
+
+    pub(crate) fn with_runtime(slurmdb: crate::SlurmDB, runtime: Runtime) -> Self {
+        SlurmDB {
+            inner: slurmdb,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client requests are sent with.
+    pub fn as_async(&self) -> &crate::SlurmDB {
+        &self.inner
+    }
+
+    /// Set how errors Slurm reports in an otherwise successful response
+    /// are handled. Defaults to `ErrorPolicy::Strict`.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.inner = self.inner.with_error_policy(error_policy);
+        self
+    }
+
+    /// Version of the REST API requests are sent to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.inner.api_version()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Get all accounts
+    pub fn get_accounts(&self) -> Result<AccountsResponse> {
+        self.block_on(self.inner.get_accounts())
+    }
+
+    /// Get a specific account
+    pub fn get_account(&self, account: &str) -> Result<AccountsResponse> {
+        self.block_on(self.inner.get_account(account))
+    }
+
+    /// Get all associations
+    pub fn get_associations(&self) -> Result<AssociationsResponse> {
+        self.block_on(self.inner.get_associations())
+    }
+
+    /// Get all clusters
+    pub fn get_clusters(&self) -> Result<ClustersResponse> {
+        self.block_on(self.inner.get_clusters())
+    }
+
+    /// Get all QOS
+    pub fn get_qos(&self) -> Result<QosResponse> {
+        self.block_on(self.inner.get_qos())
+    }
+
+    /// Get all TRES
+    pub fn get_tres(&self) -> Result<TresResponse> {
+        self.block_on(self.inner.get_tres())
+    }
+
+    /// Get all users
+    pub fn get_users(&self) -> Result<UsersResponse> {
+        self.block_on(self.inner.get_users())
+    }
+
+    /// Get a specific user
+    pub fn get_user(&self, user: &str) -> Result<UsersResponse> {
+        self.block_on(self.inner.get_user(user))
+    }
+
+    /// Get all wckeys
+    pub fn get_wckeys(&self) -> Result<WckeysResponse> {
+        self.block_on(self.inner.get_wckeys())
+    }
+
+    /// Get the full slurmdbd configuration
+    pub fn get_config(&self) -> Result<DbConfig> {
+        self.block_on(self.inner.get_config())
+    }
+
+    /// Get slurmdbd diagnostics information
+    pub fn get_diag(&self) -> Result<DbDiag> {
+        self.block_on(self.inner.get_diag())
+    }
+
+    /// Get accounting information for jobs matching the query.
+    pub fn get_jobs(&self, query: &DbJobsQuery) -> Result<DbJobsResponse> {
+        self.block_on(self.inner.get_jobs(query))
+    }
+
+    /// Get accounting information for a specific job
+    pub fn get_job(&self, job: &str) -> Result<DbJobsResponse> {
+        self.block_on(self.inner.get_job(job))
+    }
+}
+
+// A runtime for one client. The reqwest and unix socket connections only
+// make progress while a call is blocked on it, which is all they need.
+pub(crate) fn runtime() -> Result<Runtime> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| SlurmError::Config(format!("unable to start a runtime: {e}")))
+}
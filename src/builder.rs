@@ -0,0 +1,450 @@
+//! Fallible construction of `SlurmClient`, `Slurm` and `SlurmDB`.
+use crate::{
+    discovery::{self, Plugins},
+    throttle::Throttle,
+    transport::Transport,
+    ApiVersion, CredentialProvider, Credentials, ErrorPolicy, Result, RetryPolicy, Slurm,
+    SlurmClient, SlurmDB, SlurmError,
+};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, USER_AGENT},
+    Certificate, Client, Identity, Proxy, Url,
+};
+use std::{env, marker::PhantomData, sync::Arc, time::Duration};
+
+#[cfg(feature = "blocking")]
+use crate::blocking;
+#[cfg(unix)]
+use crate::unix::UnixSocket;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+/// Builder for `SlurmClient`, `Slurm` and `SlurmDB`. Create one with
+/// `SlurmClient::builder()`, `Slurm::builder()` or `SlurmDB::builder()`.
+/// Nothing is validated until `build()` or `connect()`, which return an
+/// error instead of panicking.
+pub struct SlurmBuilder<C> {
+    user: Option<String>,
+    token: Option<String>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
+    local_auth: bool,
+    endpoint: Option<String>,
+    version: Option<ApiVersion>,
+    error_policy: ErrorPolicy,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<(f64, u32)>,
+    max_in_flight: Option<usize>,
+    client: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    root_certificates: Vec<Certificate>,
+    identity: Option<Identity>,
+    proxies: Vec<Proxy>,
+    default_headers: HeaderMap,
+    #[cfg(unix)]
+    unix_socket: Option<PathBuf>,
+    _client_type: PhantomData<C>,
+}
+
+/// Builder for `SlurmDB`.
+pub type SlurmDBBuilder = SlurmBuilder<SlurmDB>;
+
+impl<C> SlurmBuilder<C> {
+    pub(crate) fn new() -> Self {
+        SlurmBuilder {
+            user: None,
+            token: None,
+            credentials: None,
+            local_auth: false,
+            endpoint: None,
+            version: None,
+            error_policy: ErrorPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            max_in_flight: None,
+            client: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            root_certificates: Vec::new(),
+            identity: None,
+            proxies: Vec::new(),
+            default_headers: HeaderMap::new(),
+            #[cfg(unix)]
+            unix_socket: None,
+            _client_type: PhantomData,
+        }
+    }
+
+    /// Fill in the endpoint, user and token from `X_SLURM_ENDPOINT`,
+    /// `X_SLURM_USER_NAME` and `X_SLURM_USER_TOKEN`. Variables that are
+    /// not set are skipped, so `build()` reports which one is missing.
+    pub fn load_env(mut self) -> Self {
+        if let Ok(endpoint) = env::var("X_SLURM_ENDPOINT") {
+            self.endpoint = Some(endpoint);
+        }
+        if let Ok(user) = env::var("X_SLURM_USER_NAME") {
+            self.user = Some(user);
+        }
+        if let Ok(token) = env::var("X_SLURM_USER_TOKEN") {
+            self.token = Some(token);
+        }
+        self
+    }
+
+    /// User to authenticate as.
+    pub fn user<S: ToString>(mut self, user: S) -> Self {
+        self.user = Some(user.to_string());
+        self
+    }
+
+    /// Token to authenticate with, e.g. from `scontrol token`.
+    pub fn token<S: ToString>(mut self, token: S) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Where to get the user and token for each request, instead of the
+    /// fixed ones from `user()` and `token()`, which it takes precedence
+    /// over. See `Jwt`, `EnvCredentials`, `FileCredentials` and
+    /// `CommandCredentials`.
+    pub fn credentials<P: CredentialProvider + 'static>(mut self, provider: P) -> Self {
+        self.credentials = Some(Arc::new(provider));
+        self
+    }
+
+    /// Send no user name or token, for a slurmrestd running with
+    /// `auth/local` that trusts whoever connects to its unix socket.
+    /// `user()`, `token()` and `credentials()` are ignored.
+    pub fn local_auth(mut self) -> Self {
+        self.local_auth = true;
+        self
+    }
+
+    /// Base URL of slurmrestd, e.g. `https://slurm.example.com:6820`.
+    pub fn endpoint<S: ToString>(mut self, endpoint: S) -> Self {
+        self.endpoint = Some(endpoint.to_string());
+        self
+    }
+
+    /// Connect to slurmrestd over the unix socket at `path`, e.g.
+    /// `/run/slurmrestd.sock`, instead of an endpoint. Of the transport
+    /// settings only the timeouts, `user_agent()` and `default_headers()`
+    /// apply to a socket.
+    #[cfg(unix)]
+    pub fn unix_socket<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.unix_socket = Some(path.into());
+        self
+    }
+
+    /// Version of the REST API to use. `build()` defaults to `v0.0.38`,
+    /// while `connect()` picks the newest version slurmrestd offers.
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// How errors reported in an otherwise successful response are handled.
+    pub fn error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// When failed requests are sent again. Defaults to never.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Send at most `per_second` requests per second on average, allowing
+    /// bursts of up to `burst` requests. Requests over the budget wait
+    /// their turn. Shared by every handle derived from the same client.
+    pub fn rate_limit(mut self, per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((per_second, burst));
+        self
+    }
+
+    /// Have at most `max` requests waiting on slurmrestd at once, counting
+    /// each until its response has been read. Shared by every handle
+    /// derived from the same client.
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max);
+        self
+    }
+
+    /// Use a preconfigured `reqwest::Client`. This can not be combined with
+    /// any of the other transport settings on this builder.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Timeout for a whole request, from connecting to reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for connecting to slurmrestd.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// `User-Agent` header sent with every request.
+    pub fn user_agent<S: ToString>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Trust an additional root certificate, e.g. a site CA.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Client certificate to present to slurmrestd.
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Send requests through a proxy.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Headers sent with every request, on top of the auth headers.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    // Validate everything and build the shared client.
+    fn into_client(self) -> Result<SlurmClient> {
+        let (transport, error_policy) = self.into_transport()?;
+        Ok(SlurmClient {
+            transport: Arc::new(transport),
+            error_policy,
+        })
+    }
+
+    // Like `into_client`, but ask slurmrestd which API versions `plugins`
+    // offer and use the newest one this crate supports.
+    async fn into_connected_client(self, plugins: Plugins) -> Result<SlurmClient> {
+        let wanted = self.version;
+        let (mut transport, error_policy) = self.into_transport()?;
+        transport.version = discovery::discover(&transport, plugins, wanted).await?;
+        Ok(SlurmClient {
+            transport: Arc::new(transport),
+            error_policy,
+        })
+    }
+
+    fn into_transport(self) -> Result<(Transport, ErrorPolicy)> {
+        if self
+            .rate_limit
+            .is_some_and(|(rate, _)| !(rate.is_finite() && rate > 0.0))
+        {
+            return Err(SlurmError::Config(
+                "rate limit must be a positive number of requests per second".into(),
+            ));
+        }
+        if self.max_in_flight == Some(0) {
+            return Err(SlurmError::Config(
+                "max_in_flight must allow at least one request".into(),
+            ));
+        }
+
+        #[cfg(unix)]
+        let socket = self.unix_socket.is_some();
+        #[cfg(not(unix))]
+        let socket = false;
+
+        let endpoint = match self.endpoint {
+            Some(endpoint) => endpoint,
+            // Only the path matters when talking over a socket.
+            None if socket => "http://localhost/".to_string(),
+            None => {
+                return Err(SlurmError::Config(
+                    "missing endpoint, set X_SLURM_ENDPOINT or call endpoint()".into(),
+                ))
+            }
+        };
+        let credentials: Option<Arc<dyn CredentialProvider>> = match self.credentials {
+            _ if self.local_auth => None,
+            Some(provider) => Some(provider),
+            None => {
+                let user = self.user.ok_or_else(|| {
+                    SlurmError::Config("missing user, set X_SLURM_USER_NAME or call user()".into())
+                })?;
+                let token = self.token.ok_or_else(|| {
+                    SlurmError::Config(
+                        "missing token, set X_SLURM_USER_TOKEN or call token()".into(),
+                    )
+                })?;
+                Some(Arc::new(Credentials::new(user, token)))
+            }
+        };
+        let endpoint = Url::parse(&endpoint)?;
+
+        let customized = self.timeout.is_some()
+            || self.connect_timeout.is_some()
+            || self.user_agent.is_some()
+            || !self.root_certificates.is_empty()
+            || self.identity.is_some()
+            || !self.proxies.is_empty()
+            || !self.default_headers.is_empty();
+
+        if socket
+            && (self.client.is_some()
+                || !self.root_certificates.is_empty()
+                || self.identity.is_some()
+                || !self.proxies.is_empty())
+        {
+            return Err(SlurmError::Config(
+                "TLS, proxy and client settings do not apply to a unix socket".into(),
+            ));
+        }
+        #[cfg(unix)]
+        let unix_socket = match self.unix_socket {
+            Some(path) => {
+                let mut headers = self.default_headers.clone();
+                if let Some(user_agent) = &self.user_agent {
+                    headers.insert(USER_AGENT, HeaderValue::from_str(user_agent)?);
+                }
+                Some(UnixSocket {
+                    path,
+                    timeout: self.timeout,
+                    connect_timeout: self.connect_timeout,
+                    headers,
+                })
+            }
+            None => None,
+        };
+
+        let client = match self.client {
+            Some(_) if customized => {
+                return Err(SlurmError::Config(
+                    "transport settings can not be combined with a preconfigured client".into(),
+                ));
+            }
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder().default_headers(self.default_headers);
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+                if let Some(identity) = self.identity {
+                    builder = builder.identity(identity);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        let transport = Transport {
+            credentials,
+            endpoint,
+            client: Arc::new(client),
+            version: self.version.unwrap_or_default(),
+            retry_policy: self.retry_policy,
+            throttle: Throttle::new(self.rate_limit, self.max_in_flight),
+            #[cfg(unix)]
+            socket: unix_socket,
+        };
+        Ok((transport, self.error_policy))
+    }
+}
+
+impl SlurmBuilder<SlurmClient> {
+    /// Build the shared client.
+    pub fn build(self) -> Result<SlurmClient> {
+        self.into_client()
+    }
+
+    /// Build the shared client, picking the API version from the OpenAPI document
+    /// slurmrestd serves. Fails if it offers no version this crate
+    /// supports, or not the one set with `api_version()`.
+    pub async fn connect(self) -> Result<SlurmClient> {
+        self.into_connected_client(Plugins::Both).await
+    }
+}
+
+impl SlurmBuilder<Slurm> {
+    /// Build the `Slurm` client.
+    pub fn build(self) -> Result<Slurm> {
+        Ok(self.into_client()?.slurm())
+    }
+
+    /// Build the `Slurm` client, picking the API version from the OpenAPI document
+    /// slurmrestd serves. Fails if it offers no version this crate
+    /// supports, or not the one set with `api_version()`.
+    pub async fn connect(self) -> Result<Slurm> {
+        Ok(self.into_connected_client(Plugins::Slurm).await?.slurm())
+    }
+}
+
+impl SlurmBuilder<SlurmDB> {
+    /// Build the `SlurmDB` client.
+    pub fn build(self) -> Result<SlurmDB> {
+        Ok(self.into_client()?.slurmdb())
+    }
+
+    /// Build the `SlurmDB` client, picking the API version from the OpenAPI document
+    /// slurmrestd serves. Fails if it offers no version this crate
+    /// supports, or not the one set with `api_version()`.
+    pub async fn connect(self) -> Result<SlurmDB> {
+        Ok(self
+            .into_connected_client(Plugins::SlurmDB)
+            .await?
+            .slurmdb())
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl SlurmBuilder<blocking::Slurm> {
+    /// Build the blocking `Slurm` client.
+    pub fn build(self) -> Result<blocking::Slurm> {
+        blocking::Slurm::from_async(self.into_client()?.slurm())
+    }
+
+    /// Build the blocking `Slurm` client, picking the API version from the
+    /// OpenAPI document slurmrestd serves. Fails if it offers no version
+    /// this crate supports, or not the one set with `api_version()`.
+    pub fn connect(self) -> Result<blocking::Slurm> {
+        let runtime = blocking::runtime()?;
+        let client = runtime.block_on(self.into_connected_client(Plugins::Slurm))?;
+        Ok(blocking::Slurm::with_runtime(client.slurm(), runtime))
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl SlurmBuilder<blocking::SlurmDB> {
+    /// Build the blocking `SlurmDB` client.
+    pub fn build(self) -> Result<blocking::SlurmDB> {
+        blocking::SlurmDB::from_async(self.into_client()?.slurmdb())
+    }
+
+    /// Build the blocking `SlurmDB` client, picking the API version from the
+    /// OpenAPI document slurmrestd serves. Fails if it offers no version
+    /// this crate supports, or not the one set with `api_version()`.
+    pub fn connect(self) -> Result<blocking::SlurmDB> {
+        let runtime = blocking::runtime()?;
+        let client = runtime.block_on(self.into_connected_client(Plugins::SlurmDB))?;
+        Ok(blocking::SlurmDB::with_runtime(client.slurmdb(), runtime))
+    }
+}
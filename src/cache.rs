@@ -0,0 +1,444 @@
+//! Caching read endpoints for callers that ask for the same data often.
+use crate::{
+    Diag, JobSubmission, JobSubmitResponse, JobUpdate, JobsResponse, Licenses, NodeUpdate,
+    NodesResponse, PartitionsResponse, ReservationsResponse, Result, Slurm,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{sync::OnceCell, time::Instant};
+
+/// How long responses are kept unless set with `CachedSlurm::ttl`.
+const DEFAULT_TTL: Duration = Duration::from_secs(5);
+
+/// The groups of endpoints `CachedSlurm` keeps a TTL for. A single item,
+/// like one node, shares the TTL of its list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheResource {
+    Diag,
+    Jobs,
+    Licenses,
+    Nodes,
+    Partitions,
+    Reservations,
+}
+
+/// Wraps a `Slurm` client to cache the responses of read endpoints for a
+/// while. Concurrent calls for the same data share one request, and the
+/// mutating methods drop whatever they may have made stale: job changes
+/// drop cached jobs, node changes drop cached nodes and partitions.
+///
+/// Responses are shared as `Arc`s, so handing them out is cheap.
+pub struct CachedSlurm {
+    slurm: Slurm,
+    ttls: HashMap<CacheResource, Duration>,
+    diag: Slot<(), Diag>,
+    jobs: Slot<Option<String>, JobsResponse>,
+    licenses: Slot<(), Licenses>,
+    nodes: Slot<Option<String>, NodesResponse>,
+    partitions: Slot<Option<String>, PartitionsResponse>,
+    reservations: Slot<Option<String>, ReservationsResponse>,
+}
+
+impl CachedSlurm {
+    /// Cache responses from `slurm`, keeping each for 5 seconds.
+    pub fn new(slurm: Slurm) -> Self {
+        CachedSlurm {
+            slurm,
+            ttls: HashMap::new(),
+            diag: Slot::default(),
+            jobs: Slot::default(),
+            licenses: Slot::default(),
+            nodes: Slot::default(),
+            partitions: Slot::default(),
+            reservations: Slot::default(),
+        }
+    }
+
+    /// How long to keep responses for `resource`. With a TTL of zero
+    /// nothing is kept, but concurrent calls still share one request.
+    pub fn ttl(mut self, resource: CacheResource, ttl: Duration) -> Self {
+        self.ttls.insert(resource, ttl);
+        self
+    }
+
+    /// The client requests are sent with.
+    pub fn slurm(&self) -> &Slurm {
+        &self.slurm
+    }
+
+    /// Drop everything cached for `resource`.
+    pub fn invalidate(&self, resource: CacheResource) {
+        match resource {
+            CacheResource::Diag => self.diag.clear(),
+            CacheResource::Jobs => self.jobs.clear(),
+            CacheResource::Licenses => self.licenses.clear(),
+            CacheResource::Nodes => self.nodes.clear(),
+            CacheResource::Partitions => self.partitions.clear(),
+            CacheResource::Reservations => self.reservations.clear(),
+        }
+    }
+
+    /// Drop everything cached.
+    pub fn invalidate_all(&self) {
+        self.diag.clear();
+        self.jobs.clear();
+        self.licenses.clear();
+        self.nodes.clear();
+        self.partitions.clear();
+        self.reservations.clear();
+    }
+
+    fn ttl_of(&self, resource: CacheResource) -> Duration {
+        self.ttls.get(&resource).copied().unwrap_or(DEFAULT_TTL)
+    }
+
+    /// Cached `Slurm::get_diag`.
+    pub async fn get_diag(&self) -> Result<Arc<Diag>> {
+        let ttl = self.ttl_of(CacheResource::Diag);
+        self.diag.get((), ttl, || self.slurm.get_diag()).await
+    }
+
+    /// Cached `Slurm::get_jobs`.
+    pub async fn get_jobs(&self) -> Result<Arc<JobsResponse>> {
+        let ttl = self.ttl_of(CacheResource::Jobs);
+        self.jobs.get(None, ttl, || self.slurm.get_jobs()).await
+    }
+
+    /// Cached `Slurm::get_job`.
+    pub async fn get_job(&self, job: &str) -> Result<Arc<JobsResponse>> {
+        let ttl = self.ttl_of(CacheResource::Jobs);
+        let key = Some(job.to_string());
+        self.jobs.get(key, ttl, || self.slurm.get_job(job)).await
+    }
+
+    /// Cached `Slurm::get_licenses`.
+    pub async fn get_licenses(&self) -> Result<Arc<Licenses>> {
+        let ttl = self.ttl_of(CacheResource::Licenses);
+        self.licenses
+            .get((), ttl, || self.slurm.get_licenses())
+            .await
+    }
+
+    /// Cached `Slurm::get_nodes`.
+    pub async fn get_nodes(&self) -> Result<Arc<NodesResponse>> {
+        let ttl = self.ttl_of(CacheResource::Nodes);
+        self.nodes.get(None, ttl, || self.slurm.get_nodes()).await
+    }
+
+    /// Cached `Slurm::get_node`.
+    pub async fn get_node(&self, node: &str) -> Result<Arc<NodesResponse>> {
+        let ttl = self.ttl_of(CacheResource::Nodes);
+        let key = Some(node.to_string());
+        self.nodes.get(key, ttl, || self.slurm.get_node(node)).await
+    }
+
+    /// Cached `Slurm::get_partitions`.
+    pub async fn get_partitions(&self) -> Result<Arc<PartitionsResponse>> {
+        let ttl = self.ttl_of(CacheResource::Partitions);
+        self.partitions
+            .get(None, ttl, || self.slurm.get_partitions())
+            .await
+    }
+
+    /// Cached `Slurm::get_partition`.
+    pub async fn get_partition(&self, partition: &str) -> Result<Arc<PartitionsResponse>> {
+        let ttl = self.ttl_of(CacheResource::Partitions);
+        let key = Some(partition.to_string());
+        self.partitions
+            .get(key, ttl, || self.slurm.get_partition(partition))
+            .await
+    }
+
+    /// Cached `Slurm::get_reservations`.
+    pub async fn get_reservations(&self) -> Result<Arc<ReservationsResponse>> {
+        let ttl = self.ttl_of(CacheResource::Reservations);
+        self.reservations
+            .get(None, ttl, || self.slurm.get_reservations())
+            .await
+    }
+
+    /// Cached `Slurm::get_reservation`.
+    pub async fn get_reservation(&self, reservation: &str) -> Result<Arc<ReservationsResponse>> {
+        let ttl = self.ttl_of(CacheResource::Reservations);
+        let key = Some(reservation.to_string());
+        self.reservations
+            .get(key, ttl, || self.slurm.get_reservation(reservation))
+            .await
+    }
+
+    /// `Slurm::update_node`, dropping cached nodes and partitions.
+    pub async fn update_node(&self, node: &str, update: &NodeUpdate) -> Result<()> {
+        let result = self.slurm.update_node(node, update).await;
+        self.nodes.clear();
+        self.partitions.clear();
+        result
+    }
+
+    /// `Slurm::delete_node`, dropping cached nodes and partitions.
+    pub async fn delete_node(&self, node: &str) -> Result<()> {
+        let result = self.slurm.delete_node(node).await;
+        self.nodes.clear();
+        self.partitions.clear();
+        result
+    }
+
+    /// `Slurm::cancel_job`, dropping cached jobs.
+    pub async fn cancel_job(&self, job: &str, signal: Option<&str>) -> Result<()> {
+        let result = self.slurm.cancel_job(job, signal).await;
+        self.jobs.clear();
+        result
+    }
+
+    /// `Slurm::update_job`, dropping cached jobs.
+    pub async fn update_job(&self, job: &str, update: &JobUpdate) -> Result<()> {
+        let result = self.slurm.update_job(job, update).await;
+        self.jobs.clear();
+        result
+    }
+
+    /// `Slurm::submit_job`, dropping cached jobs.
+    pub async fn submit_job(&self, job: &JobSubmission) -> Result<JobSubmitResponse> {
+        let result = self.slurm.submit_job(job).await;
+        self.jobs.clear();
+        result
+    }
+}
+
+// Cached responses of one kind, by key. A cell that is not set yet is a
+// request in flight, which later callers wait for instead of sending
+// their own.
+struct Slot<K, T> {
+    entries: Mutex<HashMap<K, Arc<Entry<T>>>>,
+}
+
+// A response and when it was fetched, once the request completed.
+type Entry<T> = OnceCell<(Instant, Arc<T>)>;
+
+impl<K, T> Default for Slot<K, T> {
+    fn default() -> Self {
+        Slot {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Hash + Eq, T> Slot<K, T> {
+    async fn get<F, Fut>(&self, key: K, ttl: Duration, fetch: F) -> Result<Arc<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let cell = {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            // Drop expired responses for every key, so keys that are not
+            // asked for again do not pile up, and requests that failed
+            // with nobody waiting on them.
+            entries.retain(|_, cell| match cell.get() {
+                Some((fetched, _)) => fetched.elapsed() < ttl,
+                None => Arc::strong_count(cell) > 1,
+            });
+            entries.entry(key).or_default().clone()
+        };
+        let (_, response) = cell
+            .get_or_try_init(|| async {
+                let response = fetch().await?;
+                Result::Ok((Instant::now(), Arc::new(response)))
+            })
+            .await?;
+        Ok(response.clone())
+    }
+
+    fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NodeUpdate, SlurmError};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const TTL: Duration = Duration::from_secs(5);
+
+    // A fetch that takes a second and counts how often it ran.
+    async fn fetch(count: &AtomicUsize, value: u32) -> Result<u32> {
+        count.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        Ok(value)
+    }
+
+    fn len<K, T>(slot: &Slot<K, T>) -> usize {
+        slot.entries.lock().unwrap().len()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn responses_expire_after_the_ttl() {
+        let slot = Slot::default();
+        let count = AtomicUsize::new(0);
+        let first = slot.get((), TTL, || fetch(&count, 1)).await.unwrap();
+        tokio::time::sleep(TTL - Duration::from_millis(1)).await;
+        let cached = slot.get((), TTL, || fetch(&count, 2)).await.unwrap();
+        assert!(Arc::ptr_eq(&first, &cached));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        let fetched = slot.get((), TTL, || fetch(&count, 2)).await.unwrap();
+        assert_eq!(*fetched, 2);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_zero_ttl_keeps_nothing() {
+        let slot = Slot::default();
+        let count = AtomicUsize::new(0);
+        slot.get((), Duration::ZERO, || fetch(&count, 1))
+            .await
+            .unwrap();
+        slot.get((), Duration::ZERO, || fetch(&count, 1))
+            .await
+            .unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn concurrent_callers_share_one_fetch() {
+        let slot = Slot::default();
+        let count = AtomicUsize::new(0);
+        let (a, b, c) = tokio::join!(
+            slot.get((), TTL, || fetch(&count, 1)),
+            slot.get((), TTL, || fetch(&count, 2)),
+            slot.get((), Duration::ZERO, || fetch(&count, 3)),
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(Arc::ptr_eq(&a.unwrap(), &b.unwrap()));
+        assert_eq!(*c.unwrap(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keys_are_cached_apart() {
+        let slot = Slot::default();
+        let count = AtomicUsize::new(0);
+        let a = slot.get("a", TTL, || fetch(&count, 1)).await.unwrap();
+        let b = slot.get("b", TTL, || fetch(&count, 2)).await.unwrap();
+        assert_eq!((*a, *b), (1, 2));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_fetches_are_not_kept() {
+        let slot: Slot<&str, u32> = Slot::default();
+        let count = AtomicUsize::new(0);
+        let error = slot
+            .get("a", TTL, || async {
+                count.fetch_add(1, Ordering::SeqCst);
+                Err(SlurmError::Parse("nothing".into()))
+            })
+            .await;
+        assert!(error.is_err());
+
+        let fetched = slot.get("a", TTL, || fetch(&count, 1)).await.unwrap();
+        assert_eq!(*fetched, 1);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        // Failed entries for other keys are dropped too.
+        let _ = slot
+            .get("b", TTL, || async {
+                Err(SlurmError::Parse("nothing".into()))
+            })
+            .await;
+        slot.get("a", TTL, || fetch(&count, 1)).await.unwrap();
+        assert_eq!(len(&slot), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn expired_keys_are_evicted() {
+        let slot = Slot::default();
+        let count = AtomicUsize::new(0);
+        slot.get("a", TTL, || fetch(&count, 1)).await.unwrap();
+        slot.get("b", TTL, || fetch(&count, 2)).await.unwrap();
+        assert_eq!(len(&slot), 2);
+
+        tokio::time::sleep(TTL).await;
+        slot.get("c", TTL, || fetch(&count, 3)).await.unwrap();
+        assert_eq!(len(&slot), 1);
+    }
+
+    #[tokio::test]
+    async fn mutations_drop_what_they_make_stale() {
+        // Nothing listens on the port once the listener is gone, so every
+        // mutation fails, which must not keep the caches from being dropped.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let slurm = Slurm::builder()
+            .endpoint(format!("http://127.0.0.1:{port}/"))
+            .user("alice")
+            .token("abc")
+            .build()
+            .unwrap();
+        let cached = CachedSlurm::new(slurm);
+        let fill = || async {
+            let jobs = JobsResponse::default();
+            let nodes = NodesResponse::default();
+            let partitions = PartitionsResponse::default();
+            cached
+                .jobs
+                .get(None, TTL, || async { Ok(jobs) })
+                .await
+                .unwrap();
+            cached
+                .nodes
+                .get(None, TTL, || async { Ok(nodes) })
+                .await
+                .unwrap();
+            cached
+                .partitions
+                .get(None, TTL, || async { Ok(partitions) })
+                .await
+                .unwrap();
+        };
+        let cached_len = |cached: &CachedSlurm| {
+            (
+                len(&cached.jobs),
+                len(&cached.nodes),
+                len(&cached.partitions),
+            )
+        };
+
+        fill().await;
+        assert!(cached
+            .update_node("gpu001", &NodeUpdate::resume())
+            .await
+            .is_err());
+        assert_eq!(cached_len(&cached), (1, 0, 0));
+
+        fill().await;
+        assert!(cached.delete_node("gpu001").await.is_err());
+        assert_eq!(cached_len(&cached), (1, 0, 0));
+
+        fill().await;
+        assert!(cached.cancel_job("1234", None).await.is_err());
+        assert_eq!(cached_len(&cached), (0, 1, 1));
+
+        fill().await;
+        assert!(cached.update_job("1234", &JobUpdate::new()).await.is_err());
+        assert_eq!(cached_len(&cached), (0, 1, 1));
+
+        fill().await;
+        cached.invalidate(CacheResource::Nodes);
+        assert_eq!(cached_len(&cached), (1, 0, 1));
+        cached.invalidate_all();
+        assert_eq!(cached_len(&cached), (0, 0, 0));
+    }
+}
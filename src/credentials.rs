@@ -0,0 +1,313 @@
+//! Where the user name and token sent with each request come from.
+use crate::{jwt, Result, SlurmError};
+use async_trait::async_trait;
+use std::{
+    env, fmt,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+use tokio::{process::Command, sync::Mutex};
+
+/// A user name and token to authenticate a request with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub user: String,
+    pub token: String,
+}
+
+impl Credentials {
+    /// Authenticate as `user` with `token`.
+    pub fn new<U: ToString, T: ToString>(user: U, token: T) -> Self {
+        Credentials {
+            user: user.to_string(),
+            token: token.to_string(),
+        }
+    }
+}
+
+// Keep the token out of logs.
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user", &self.user)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Supplies the credentials for every request. Implement this to fetch
+/// tokens from somewhere the crate does not know about, e.g. a secrets
+/// manager.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Credentials for the next request. This is called for every
+    /// request, so cache anything expensive.
+    async fn credentials(&self) -> Result<Credentials>;
+
+    /// Called when slurmrestd rejected the given credentials with a 401. Drop anything cached and return `true` only if the next
+    /// `credentials()` call will return something different, in which
+    /// case the request is sent once more.
+    async fn invalidate(&self, _rejected: &Credentials) -> bool {
+        false
+    }
+}
+
+/// Fixed credentials, e.g. from `scontrol token`.
+#[async_trait]
+impl CredentialProvider for Credentials {
+    async fn credentials(&self) -> Result<Credentials> {
+        Ok(self.clone())
+    }
+}
+
+/// Reads `X_SLURM_USER_NAME` and `X_SLURM_USER_TOKEN` for every request,
+/// so a token replaced in the environment is picked up.
+#[derive(Debug, Default, Clone)]
+pub struct EnvCredentials;
+
+#[async_trait]
+impl CredentialProvider for EnvCredentials {
+    async fn credentials(&self) -> Result<Credentials> {
+        let var = |name| {
+            env::var(name).map_err(|_| SlurmError::Credentials(format!("{name} is not set")))
+        };
+        Ok(Credentials::new(
+            var("X_SLURM_USER_NAME")?,
+            var("X_SLURM_USER_TOKEN")?,
+        ))
+    }
+
+    async fn invalidate(&self, rejected: &Credentials) -> bool {
+        self.credentials().await.is_ok_and(|c| c != *rejected)
+    }
+}
+
+/// Reads the token from a file, again whenever the file changes. Useful
+/// when something else, like a cron job running `scontrol token`, keeps
+/// the file up to date. Either a bare token or the `SLURM_JWT=...` line
+/// `scontrol token` prints is accepted.
+pub struct FileCredentials {
+    user: String,
+    path: PathBuf,
+    // The token and the modification time of the file it was read from.
+    current: Mutex<Option<(String, SystemTime)>>,
+}
+
+impl FileCredentials {
+    /// Authenticate as `user` with the token in the file at `path`.
+    pub fn new<U: ToString, P: Into<PathBuf>>(user: U, path: P) -> Self {
+        FileCredentials {
+            user: user.to_string(),
+            path: path.into(),
+            current: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for FileCredentials {
+    async fn credentials(&self) -> Result<Credentials> {
+        let error = |e| SlurmError::Credentials(format!("reading {}: {e}", self.path.display()));
+
+        let mut current = self.current.lock().await;
+        let modified = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|m| m.modified())
+            .map_err(error)?;
+        if let Some((token, read)) = current.as_ref() {
+            if *read == modified {
+                return Ok(Credentials::new(&self.user, token));
+            }
+        }
+        let contents = tokio::fs::read_to_string(&self.path).await.map_err(error)?;
+        let token = parse_token(&contents).ok_or_else(|| {
+            SlurmError::Credentials(format!("{} holds no token", self.path.display()))
+        })?;
+        *current = Some((token.clone(), modified));
+        Ok(Credentials::new(&self.user, token))
+    }
+
+    async fn invalidate(&self, rejected: &Credentials) -> bool {
+        *self.current.lock().await = None;
+        self.credentials().await.is_ok_and(|c| c != *rejected)
+    }
+}
+
+/// Runs a command that prints a token, like `scontrol token`, and reuses
+/// its output until shortly before the token expires.
+pub struct CommandCredentials {
+    user: String,
+    program: String,
+    args: Vec<String>,
+    // The token and when it expires, if it is a JWT.
+    current: Mutex<Option<(String, Option<u64>)>>,
+}
+
+impl CommandCredentials {
+    /// Run `program` for a token for `user`.
+    pub fn new<U: ToString, P: ToString>(user: U, program: P) -> Self {
+        CommandCredentials {
+            user: user.to_string(),
+            program: program.to_string(),
+            args: Vec::new(),
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Run `scontrol token lifespan=<seconds>` for tokens of the user
+    /// running this process.
+    pub fn scontrol<U: ToString>(user: U, lifespan: Duration) -> Self {
+        CommandCredentials::new(user, "scontrol").args([
+            "token".to_string(),
+            format!("lifespan={}", lifespan.as_secs()),
+        ])
+    }
+
+    /// Arguments to pass to the program.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.args = args.into_iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    async fn run(&self) -> Result<String> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .output()
+            .await
+            .map_err(|e| SlurmError::Credentials(format!("running {}: {e}", self.program)))?;
+        if !output.status.success() {
+            return Err(SlurmError::Credentials(format!(
+                "{} failed with {}: {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        parse_token(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| SlurmError::Credentials(format!("{} printed no token", self.program)))
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for CommandCredentials {
+    async fn credentials(&self) -> Result<Credentials> {
+        let mut current = self.current.lock().await;
+        if let Some((token, exp)) = current.as_ref() {
+            let fresh = match exp {
+                Some(exp) => jwt::now()? + jwt::REFRESH_MARGIN.as_secs() < *exp,
+                None => true,
+            };
+            if fresh {
+                return Ok(Credentials::new(&self.user, token));
+            }
+        }
+        let token = self.run().await?;
+        *current = Some((token.clone(), jwt::expiry(&token)));
+        Ok(Credentials::new(&self.user, token))
+    }
+
+    async fn invalidate(&self, rejected: &Credentials) -> bool {
+        *self.current.lock().await = None;
+        self.credentials().await.is_ok_and(|c| c != *rejected)
+    }
+}
+
+// The token in a file or command output: either the whole thing or the
+// value of a `SLURM_JWT=` line.
+fn parse_token(output: &str) -> Option<String> {
+    let token = output
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("SLURM_JWT="))
+        .unwrap_or(output)
+        .trim();
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, process};
+
+    // A file of its own for each test, as they run in parallel.
+    fn token_file(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("slurm-rs-{}-{name}", process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn tokens_in_output() {
+        assert_eq!(parse_token("abc\n").as_deref(), Some("abc"));
+        assert_eq!(parse_token("SLURM_JWT=abc\n").as_deref(), Some("abc"));
+        assert_eq!(
+            parse_token("note: lifespan capped\nSLURM_JWT=abc\n").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(parse_token(" \n"), None);
+    }
+
+    #[tokio::test]
+    async fn fixed_credentials_are_not_retried() {
+        let credentials = Credentials::new("alice", "abc");
+        assert!(!credentials.invalidate(&credentials).await);
+    }
+
+    #[tokio::test]
+    async fn environment() {
+        env::set_var("X_SLURM_USER_NAME", "alice");
+        env::set_var("X_SLURM_USER_TOKEN", "abc");
+        let provider = EnvCredentials;
+        let rejected = provider.credentials().await.unwrap();
+        assert_eq!(rejected, Credentials::new("alice", "abc"));
+        assert!(!provider.invalidate(&rejected).await);
+
+        env::set_var("X_SLURM_USER_TOKEN", "def");
+        assert!(provider.invalidate(&rejected).await);
+        assert_eq!(provider.credentials().await.unwrap().token, "def");
+
+        env::remove_var("X_SLURM_USER_TOKEN");
+        assert!(provider.credentials().await.is_err());
+        assert!(!provider.invalidate(&rejected).await);
+    }
+
+    #[tokio::test]
+    async fn file() {
+        let path = token_file("file", "SLURM_JWT=abc\n");
+        let provider = FileCredentials::new("alice", &path);
+        let rejected = provider.credentials().await.unwrap();
+        assert_eq!(rejected, Credentials::new("alice", "abc"));
+        assert!(!provider.invalidate(&rejected).await);
+
+        // Picked up even if the modification time did not move.
+        fs::write(&path, "def").unwrap();
+        assert!(provider.invalidate(&rejected).await);
+        assert_eq!(provider.credentials().await.unwrap().token, "def");
+
+        fs::write(&path, "").unwrap();
+        assert!(provider.credentials().await.is_err());
+        fs::remove_file(&path).unwrap();
+        assert!(!provider.invalidate(&rejected).await);
+    }
+
+    #[tokio::test]
+    async fn command() {
+        let provider = CommandCredentials::new("alice", "echo").args(["SLURM_JWT=abc"]);
+        let rejected = provider.credentials().await.unwrap();
+        assert_eq!(rejected, Credentials::new("alice", "abc"));
+        assert!(!provider.invalidate(&rejected).await);
+
+        // The shell's process ID makes every token different.
+        let provider = CommandCredentials::new("alice", "sh").args(["-c", "echo $$"]);
+        let rejected = provider.credentials().await.unwrap();
+        assert_eq!(provider.credentials().await.unwrap(), rejected);
+        assert!(provider.invalidate(&rejected).await);
+        assert_ne!(provider.credentials().await.unwrap(), rejected);
+
+        let provider = CommandCredentials::new("alice", "false");
+        assert!(provider.credentials().await.is_err());
+        assert!(!provider.invalidate(&rejected).await);
+    }
+}
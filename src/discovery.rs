@@ -0,0 +1,209 @@
+//! Picking an API version from the OpenAPI document slurmrestd serves.
+use crate::{transport::Transport, ApiVersion, Result, SlurmError};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+// Where slurmrestd serves its OpenAPI document, in the order they are
+// tried. Older releases only serve `openapi`.
+const DOCUMENTS: [&str; 2] = ["openapi/v3", "openapi"];
+
+// Versions offered by the `slurm` and `slurmdb` plugins.
+#[derive(Debug, Default)]
+struct Offered {
+    slurm: BTreeSet<ApiVersion>,
+    slurmdb: BTreeSet<ApiVersion>,
+    // Versions in the document this crate does not know, for errors.
+    unknown: BTreeSet<String>,
+}
+
+// The plugins a client is going to talk to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Plugins {
+    Slurm,
+    SlurmDB,
+    // A `SlurmClient`, which hands out both.
+    Both,
+}
+
+impl Plugins {
+    fn name(&self) -> &'static str {
+        match self {
+            Plugins::Slurm => "the slurm plugin",
+            Plugins::SlurmDB => "the slurmdb plugin",
+            Plugins::Both => "both the slurm and slurmdb plugins",
+        }
+    }
+}
+
+// Fetch the OpenAPI document and pick the version `plugins` talk. If
+// `wanted` is set it is only checked against what slurmrestd offers.
+pub(crate) async fn discover(
+    transport: &Transport,
+    plugins: Plugins,
+    wanted: Option<ApiVersion>,
+) -> Result<ApiVersion> {
+    let mut document = None;
+    for path in DOCUMENTS {
+        document = transport.document(path).await?;
+        if document.is_some() {
+            break;
+        }
+    }
+    let document = document.ok_or_else(|| {
+        SlurmError::Config(format!(
+            "slurmrestd at {} does not serve an OpenAPI document",
+            transport.endpoint
+        ))
+    })?;
+
+    let offered = offered(&document);
+    select(&offered, plugins, wanted).ok_or_else(|| {
+        let mut versions: Vec<String> = offered
+            .versions(plugins)
+            .iter()
+            .map(ApiVersion::to_string)
+            .collect();
+        versions.extend(offered.unknown.iter().cloned());
+        let versions = if versions.is_empty() {
+            "no versions".to_string()
+        } else {
+            versions.join(", ")
+        };
+        let supported = ApiVersion::ALL.map(|v| v.as_str()).join(", ");
+        let message = match wanted {
+            Some(wanted) => format!(
+                "slurmrestd at {} does not offer {wanted} for {} (it offers {versions})",
+                transport.endpoint,
+                plugins.name()
+            ),
+            None => format!(
+                "slurmrestd at {} offers {versions} for {}, none of which this crate supports ({supported})",
+                transport.endpoint,
+                plugins.name()
+            ),
+        };
+        SlurmError::Config(message)
+    })
+}
+
+impl Offered {
+    // Versions every plugin in `plugins` offers. A `SlurmClient` also
+    // works with slurmrestd running only one of them.
+    fn versions(&self, plugins: Plugins) -> BTreeSet<ApiVersion> {
+        match plugins {
+            Plugins::Slurm => self.slurm.clone(),
+            Plugins::SlurmDB => self.slurmdb.clone(),
+            Plugins::Both if self.slurm.is_empty() || self.slurmdb.is_empty() => {
+                self.slurm.union(&self.slurmdb).copied().collect()
+            }
+            Plugins::Both => self.slurm.intersection(&self.slurmdb).copied().collect(),
+        }
+    }
+}
+
+// Collect the plugin versions from the document's paths, which look like
+// `/slurm/v0.0.38/diag` or `/slurmdb/v0.0.38/jobs`.
+fn offered(document: &Value) -> Offered {
+    let mut offered = Offered::default();
+    let Some(paths) = document.get("paths").and_then(Value::as_object) else {
+        return offered;
+    };
+    for path in paths.keys() {
+        let mut parts = path.trim_start_matches('/').split('/');
+        let (Some(plugin), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let versions = match plugin {
+            "slurm" => &mut offered.slurm,
+            "slurmdb" => &mut offered.slurmdb,
+            _ => continue,
+        };
+        match version.parse() {
+            Ok(version) => {
+                versions.insert(version);
+            }
+            Err(_) => {
+                offered.unknown.insert(version.to_string());
+            }
+        }
+    }
+    offered
+}
+
+// `wanted` if `plugins` offer it, otherwise the newest version they offer.
+fn select(offered: &Offered, plugins: Plugins, wanted: Option<ApiVersion>) -> Option<ApiVersion> {
+    let versions = offered.versions(plugins);
+    match wanted {
+        Some(wanted) => versions.contains(&wanted).then_some(wanted),
+        None => versions.last().copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use ApiVersion::*;
+
+    fn document(paths: &[&str]) -> Value {
+        let paths: serde_json::Map<String, Value> =
+            paths.iter().map(|p| (p.to_string(), json!({}))).collect();
+        json!({ "paths": paths })
+    }
+
+    #[test]
+    fn collects_versions_per_plugin() {
+        let offered = offered(&document(&[
+            "/slurm/v0.0.39/jobs",
+            "/slurm/v0.0.40/jobs",
+            "/slurmdb/v0.0.39/jobs",
+            "/slurm/v0.0.99/jobs",
+            "/openapi/v3",
+        ]));
+        assert_eq!(offered.slurm, [V0_0_39, V0_0_40].into());
+        assert_eq!(offered.slurmdb, [V0_0_39].into());
+        assert_eq!(offered.unknown, ["v0.0.99".to_string()].into());
+    }
+
+    #[test]
+    fn picks_the_newest_version_of_the_plugin() {
+        let offered = offered(&document(&[
+            "/slurm/v0.0.39/jobs",
+            "/slurm/v0.0.40/jobs",
+            "/slurmdb/v0.0.38/jobs",
+            "/slurmdb/v0.0.39/jobs",
+        ]));
+        assert_eq!(select(&offered, Plugins::Slurm, None), Some(V0_0_40));
+        assert_eq!(select(&offered, Plugins::SlurmDB, None), Some(V0_0_39));
+        assert_eq!(select(&offered, Plugins::Both, None), Some(V0_0_39));
+    }
+
+    #[test]
+    fn wanted_must_be_offered_by_the_plugin() {
+        let offered = offered(&document(&["/slurm/v0.0.40/jobs", "/slurmdb/v0.0.38/jobs"]));
+        assert_eq!(
+            select(&offered, Plugins::Slurm, Some(V0_0_40)),
+            Some(V0_0_40)
+        );
+        assert_eq!(select(&offered, Plugins::SlurmDB, Some(V0_0_40)), None);
+        assert_eq!(
+            select(&offered, Plugins::SlurmDB, Some(V0_0_38)),
+            Some(V0_0_38)
+        );
+        assert_eq!(select(&offered, Plugins::Both, Some(V0_0_40)), None);
+    }
+
+    #[test]
+    fn plugins_without_a_common_version() {
+        let offered = offered(&document(&["/slurm/v0.0.40/jobs", "/slurmdb/v0.0.38/jobs"]));
+        assert_eq!(select(&offered, Plugins::Both, None), None);
+        assert_eq!(select(&offered, Plugins::Slurm, None), Some(V0_0_40));
+    }
+
+    #[test]
+    fn without_slurmdb() {
+        let offered = offered(&document(&["/slurm/v0.0.39/jobs", "/slurm/v0.0.40/jobs"]));
+        assert_eq!(select(&offered, Plugins::Both, None), Some(V0_0_40));
+        assert_eq!(select(&offered, Plugins::SlurmDB, None), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! Names for the error numbers Slurm puts in `Error::error_number`.
+//!
+//! SEE: <https://github.com/SchedMD/slurm/blob/master/slurm/slurm_errno.h>
+
+// Returned when data asked for with an `update_time` did not change.
+pub(crate) const SLURM_NO_CHANGE_IN_DATA: i32 = 1900;
+
+// Sorted by number so we can binary search it.
+const ERRNO_NAMES: &[(i32, &str)] = &[
+    (-1, "SLURM_ERROR"),
+    (0, "SLURM_SUCCESS"),
+    (1000, "SLURM_UNEXPECTED_MSG_ERROR"),
+    (1001, "SLURM_COMMUNICATIONS_CONNECTION_ERROR"),
+    (1002, "SLURM_COMMUNICATIONS_SEND_ERROR"),
+    (1003, "SLURM_COMMUNICATIONS_RECEIVE_ERROR"),
+    (1004, "SLURM_COMMUNICATIONS_SHUTDOWN_ERROR"),
+    (1005, "SLURM_PROTOCOL_VERSION_ERROR"),
+    (1006, "SLURM_PROTOCOL_IO_STREAM_VERSION_ERROR"),
+    (1007, "SLURM_PROTOCOL_AUTHENTICATION_ERROR"),
+    (1008, "SLURM_PROTOCOL_INSANE_MSG_LENGTH"),
+    (1800, "SLURMCTLD_COMMUNICATIONS_CONNECTION_ERROR"),
+    (1801, "SLURMCTLD_COMMUNICATIONS_SEND_ERROR"),
+    (1802, "SLURMCTLD_COMMUNICATIONS_RECEIVE_ERROR"),
+    (1803, "SLURMCTLD_COMMUNICATIONS_SHUTDOWN_ERROR"),
+    (1804, "SLURMCTLD_COMMUNICATIONS_BACKOFF"),
+    (1900, "SLURM_NO_CHANGE_IN_DATA"),
+    (2000, "ESLURM_INVALID_PARTITION_NAME"),
+    (2001, "ESLURM_DEFAULT_PARTITION_NOT_SET"),
+    (2002, "ESLURM_ACCESS_DENIED"),
+    (2003, "ESLURM_JOB_MISSING_REQUIRED_PARTITION_GROUP"),
+    (2004, "ESLURM_REQUESTED_NODES_NOT_IN_PARTITION"),
+    (2005, "ESLURM_TOO_MANY_REQUESTED_CPUS"),
+    (2006, "ESLURM_INVALID_NODE_COUNT"),
+    (2007, "ESLURM_ERROR_ON_DESC_TO_RECORD_COPY"),
+    (2008, "ESLURM_JOB_MISSING_SIZE_SPECIFICATION"),
+    (2009, "ESLURM_JOB_SCRIPT_MISSING"),
+    (2010, "ESLURM_USER_ID_MISSING"),
+    (2011, "ESLURM_DUPLICATE_JOB_ID"),
+    (2012, "ESLURM_PATHNAME_TOO_LONG"),
+    (2013, "ESLURM_NOT_TOP_PRIORITY"),
+    (2014, "ESLURM_REQUESTED_NODE_CONFIG_UNAVAILABLE"),
+    (2015, "ESLURM_REQUESTED_PART_CONFIG_UNAVAILABLE"),
+    (2016, "ESLURM_NODES_BUSY"),
+    (2017, "ESLURM_INVALID_JOB_ID"),
+    (2018, "ESLURM_INVALID_NODE_NAME"),
+    (2019, "ESLURM_WRITING_TO_FILE"),
+    (2020, "ESLURM_TRANSITION_STATE_NO_UPDATE"),
+    (2021, "ESLURM_ALREADY_DONE"),
+    (2022, "ESLURM_INTERCONNECT_FAILURE"),
+    (2023, "ESLURM_BAD_DIST"),
+    (2024, "ESLURM_JOB_PENDING"),
+    (2025, "ESLURM_BAD_TASK_COUNT"),
+    (2026, "ESLURM_INVALID_JOB_CREDENTIAL"),
+    (2027, "ESLURM_IN_STANDBY_MODE"),
+    (2028, "ESLURM_INVALID_NODE_STATE"),
+    (2029, "ESLURM_INVALID_FEATURE"),
+    (2030, "ESLURM_INVALID_AUTHTYPE_CHANGE"),
+    (2031, "ESLURM_ACTIVE_FEATURE_NOT_SUBSET"),
+    (2032, "ESLURM_INVALID_SCHEDTYPE_CHANGE"),
+    (2033, "ESLURM_INVALID_SELECTTYPE_CHANGE"),
+    (2034, "ESLURM_INVALID_SWITCHTYPE_CHANGE"),
+    (2035, "ESLURM_FRAGMENTATION"),
+    (2036, "ESLURM_NOT_SUPPORTED"),
+    (2037, "ESLURM_DISABLED"),
+    (2038, "ESLURM_DEPENDENCY"),
+    (2039, "ESLURM_BATCH_ONLY"),
+    (2040, "ESLURM_TASKDIST_ARBITRARY_UNSUPPORTED"),
+    (2041, "ESLURM_TASKDIST_REQUIRES_OVERCOMMIT"),
+    (2042, "ESLURM_JOB_HELD"),
+    (2044, "ESLURM_INVALID_TASK_MEMORY"),
+    (2045, "ESLURM_INVALID_ACCOUNT"),
+    (2046, "ESLURM_INVALID_PARENT_ACCOUNT"),
+    (2047, "ESLURM_SAME_PARENT_ACCOUNT"),
+    (2048, "ESLURM_INVALID_LICENSES"),
+    (2049, "ESLURM_NEED_RESTART"),
+    (2050, "ESLURM_ACCOUNTING_POLICY"),
+    (2051, "ESLURM_INVALID_TIME_LIMIT"),
+    (2052, "ESLURM_RESERVATION_ACCESS"),
+    (2053, "ESLURM_RESERVATION_INVALID"),
+    (2054, "ESLURM_INVALID_TIME_VALUE"),
+    (2055, "ESLURM_RESERVATION_BUSY"),
+    (2056, "ESLURM_RESERVATION_NOT_USABLE"),
+    (2057, "ESLURM_INVALID_WCKEY"),
+    (2058, "ESLURM_RESERVATION_OVERLAP"),
+    (2059, "ESLURM_PORTS_BUSY"),
+    (2060, "ESLURM_PORTS_INVALID"),
+    (2061, "ESLURM_PROLOG_RUNNING"),
+    (2062, "ESLURM_NO_STEPS"),
+    (2066, "ESLURM_INVALID_QOS"),
+    (2067, "ESLURM_QOS_PREEMPTION_LOOP"),
+    (2068, "ESLURM_NODE_NOT_AVAIL"),
+    (2069, "ESLURM_INVALID_CPU_COUNT"),
+    (2070, "ESLURM_PARTITION_NOT_AVAIL"),
+    (2071, "ESLURM_CIRCULAR_DEPENDENCY"),
+    (2072, "ESLURM_INVALID_GRES"),
+    (2073, "ESLURM_JOB_NOT_PENDING"),
+    (2074, "ESLURM_QOS_THRES"),
+    (2075, "ESLURM_PARTITION_IN_USE"),
+    (2076, "ESLURM_STEP_LIMIT"),
+    (2077, "ESLURM_JOB_SUSPENDED"),
+    (2078, "ESLURM_CAN_NOT_START_IMMEDIATELY"),
+    (2079, "ESLURM_INTERCONNECT_BUSY"),
+    (2080, "ESLURM_RESERVATION_EMPTY"),
+    (2081, "ESLURM_INVALID_ARRAY"),
+    (2082, "ESLURM_RESERVATION_NAME_DUP"),
+    (2083, "ESLURM_JOB_STARTED"),
+    (2084, "ESLURM_JOB_FINISHED"),
+    (2085, "ESLURM_JOB_NOT_RUNNING"),
+    (2086, "ESLURM_JOB_NOT_PENDING_NOR_RUNNING"),
+    (2087, "ESLURM_JOB_NOT_SUSPENDED"),
+    (2088, "ESLURM_JOB_NOT_FINISHED"),
+    (2089, "ESLURM_TRIGGER_DUP"),
+    (2090, "ESLURM_INTERNAL"),
+    (2091, "ESLURM_INVALID_BURST_BUFFER_CHANGE"),
+    (2092, "ESLURM_BURST_BUFFER_PERMISSION"),
+    (2093, "ESLURM_BURST_BUFFER_LIMIT"),
+    (2094, "ESLURM_INVALID_BURST_BUFFER_REQUEST"),
+    (2095, "ESLURM_PRIO_RESET_FAIL"),
+    (2099, "ESLURM_INVALID_MCS_LABEL"),
+    (2100, "ESLURM_BURST_BUFFER_WAIT"),
+    (2101, "ESLURM_PARTITION_DOWN"),
+    (2102, "ESLURM_DUPLICATE_GRES"),
+    (2103, "ESLURM_JOB_SETTING_DB_INX"),
+    (2104, "ESLURM_RSV_ALREADY_STARTED"),
+    (2105, "ESLURM_SUBMISSIONS_DISABLED"),
+    (2106, "ESLURM_NOT_HET_JOB"),
+    (2107, "ESLURM_NOT_HET_JOB_LEADER"),
+    (2108, "ESLURM_NOT_WHOLE_HET_JOB"),
+    (2109, "ESLURM_CORE_RESERVATION_UPDATE"),
+    (2110, "ESLURM_DUPLICATE_STEP_ID"),
+    (2111, "ESLURM_INVALID_CORE_CNT"),
+    (2112, "ESLURM_X11_NOT_AVAIL"),
+    (2113, "ESLURM_GROUP_ID_MISSING"),
+    (2114, "ESLURM_BATCH_CONSTRAINT"),
+    (2115, "ESLURM_INVALID_TRES"),
+    (2116, "ESLURM_INVALID_TRES_BILLING_WEIGHTS"),
+    (2117, "ESLURM_INVALID_JOB_DEFAULTS"),
+    (2118, "ESLURM_RESERVATION_MAINT"),
+    (2119, "ESLURM_INVALID_GRES_TYPE"),
+    (2120, "ESLURM_REBOOT_IN_PROGRESS"),
+    (2121, "ESLURM_MULTI_KNL_CONSTRAINT"),
+    (2122, "ESLURM_UNSUPPORTED_GRES"),
+    (2123, "ESLURM_INVALID_NICE"),
+    (2124, "ESLURM_INVALID_TIME_MIN_LIMIT"),
+    (2125, "ESLURM_DEFER"),
+    (2126, "ESLURM_CONFIGLESS_DISABLED"),
+    (2127, "ESLURM_ENVIRONMENT_MISSING"),
+    (2128, "ESLURM_RESERVATION_NO_SKIP"),
+    (2129, "ESLURM_RESERVATION_USER_GROUP"),
+    (2130, "ESLURM_PARTITION_ASSOC"),
+    (2131, "ESLURM_IN_STANDBY_USE_BACKUP"),
+    (2132, "ESLURM_BAD_THREAD_PER_CORE"),
+    (2133, "ESLURM_INVALID_PREFER"),
+    (2134, "ESLURM_INSUFFICIENT_GRES"),
+    (6000, "ESLURM_AUTH_CRED_INVALID"),
+    (6004, "ESLURM_AUTH_BADARG"),
+    (6005, "ESLURM_AUTH_UNPACK"),
+    (6006, "ESLURM_AUTH_SKIP"),
+    (6007, "ESLURM_AUTH_UNABLE_TO_GENERATE_TOKEN"),
+    (7000, "ESLURM_DB_CONNECTION"),
+    (7001, "ESLURM_JOBS_RUNNING_ON_ASSOC"),
+    (7002, "ESLURM_CLUSTER_DELETED"),
+    (7003, "ESLURM_ONE_CHANGE"),
+    (7004, "ESLURM_BAD_NAME"),
+    (7005, "ESLURM_OVER_ALLOCATE"),
+    (7006, "ESLURM_RESULT_TOO_LARGE"),
+    (7007, "ESLURM_DB_QUERY_TOO_WIDE"),
+    (7008, "ESLURM_DB_CONNECTION_INVALID"),
+    (7009, "ESLURM_NO_REMOVE_DEFAULT_ACCOUNT"),
+    (9000, "ESLURM_REST_INVALID_QUERY"),
+    (9001, "ESLURM_REST_FAIL_PARSING"),
+    (9002, "ESLURM_REST_INVALID_JOBS_DESC"),
+    (9003, "ESLURM_REST_EMPTY_RESULT"),
+    (9004, "ESLURM_REST_MISSING_UID"),
+    (9005, "ESLURM_REST_MISSING_GID"),
+    (9200, "ESLURM_DATA_PATH_NOT_FOUND"),
+    (9201, "ESLURM_DATA_PTR_NULL"),
+    (9202, "ESLURM_DATA_CONV_FAILED"),
+    (9203, "ESLURM_DATA_REGEX_COMPILE"),
+    (9204, "ESLURM_DATA_UNKNOWN_MIME_TYPE"),
+    (9205, "ESLURM_DATA_TOO_LARGE"),
+];
+
+// Look up the name of a Slurm errno.
+pub(crate) fn name(errno: i32) -> Option<&'static str> {
+    ERRNO_NAMES
+        .binary_search_by_key(&errno, |&(n, _)| n)
+        .ok()
+        .map(|i| ERRNO_NAMES[i].1)
+}
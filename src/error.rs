@@ -0,0 +1,107 @@
+//! Errors returned by the Slurm and SlurmDB clients.
+use crate::{ApiVersion, Error};
+use reqwest::StatusCode;
+
+/// Result type used by every endpoint method.
+pub type Result<T> = std::result::Result<T, SlurmError>;
+
+/// Everything that can go wrong while talking to slurmrestd.
+#[derive(Debug, thiserror::Error)]
+pub enum SlurmError {
+    /// The request could not be sent or the response could not be read.
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    /// slurmrestd answered with a status other than 200. `errors` holds
+    /// whatever Slurm errors could be parsed out of the body.
+    #[error("status code: {status}, body: {body}")]
+    Http {
+        status: StatusCode,
+        errors: Vec<Error>,
+        body: String,
+    },
+
+    /// The response body did not match the expected type. `path` points
+    /// at the field that failed, e.g. `partitions[0].allowed_accounts`.
+    #[error("unable to deserialize response at '{path}': {source}")]
+    Deserialize {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// The request body could not be serialized.
+    #[error("unable to serialize request: {0}")]
+    Serialize(#[source] serde_json::Error),
+
+    /// The endpoint could not be turned into a valid URL.
+    #[error("invalid endpoint: {0}")]
+    InvalidEndpoint(#[from] url::ParseError),
+
+    /// Talking to slurmrestd over a unix socket failed.
+    #[error("unix socket error: {0}")]
+    Socket(#[from] std::io::Error),
+
+    /// A user name or token could not be used as a header value.
+    #[error("invalid header value: {0}")]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+
+    /// The client could not be built from the given settings.
+    #[error("invalid configuration: {0}")]
+    Config(String),
+
+    /// A credential provider could not produce a user and token.
+    #[error("unable to get credentials: {0}")]
+    Credentials(String),
+
+    /// A value Slurm uses a notation for, like a hostlist, could not be
+    /// parsed.
+    #[error("unable to parse {0}")]
+    Parse(String),
+
+    /// The request was rejected before being sent.
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+
+    /// slurmrestd answered with a different API version than the client
+    /// was built for.
+    #[error("expected a response from API {expected}, got one from {actual}")]
+    VersionMismatch {
+        expected: ApiVersion,
+        actual: ApiVersion,
+    },
+
+    /// Slurm handled the request but reported errors.
+    #[error("slurm returned errors: {}", format_errors(errors))]
+    Slurm { errors: Vec<Error> },
+}
+
+impl SlurmError {
+    /// Status code of the response, if the error came from one.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            SlurmError::Http { status, .. } => Some(*status),
+            SlurmError::Transport(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// Errors reported by Slurm, if any.
+    pub fn slurm_errors(&self) -> &[Error] {
+        match self {
+            SlurmError::Http { errors, .. } | SlurmError::Slurm { errors } => errors,
+            _ => &[],
+        }
+    }
+}
+
+fn format_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(|e| match e.errno_name() {
+            Some(name) => format!("{} ({name})", e.error),
+            None => format!("{} (error number {})", e.error, e.error_number),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,344 @@
+//! Slurm GRES (generic resource) strings, like `gpu:a100:4(S:0-1),nvme:1`.
+use crate::{JobResponseProperties, Node, Result, SlurmError};
+use std::{fmt, str::FromStr};
+
+// The most indices one entry may list, which keeps a mistyped range like
+// `(IDX:0-4294967295)` from expanding into billions of numbers. Nodes have
+// far fewer devices or sockets than this.
+const MAX_INDICES: u64 = 64 * 1024;
+
+/// One generic resource, parsed from or written as an entry of a Slurm
+/// GRES string, like `gpu:a100:4(S:0-1)` on a node or `gpu:a100:2(IDX:0,2)`
+/// in a job's allocation.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Gres {
+    /// The resource, like `gpu`.
+    pub name: String,
+    /// The type, like `a100`, if the resource has types.
+    pub kind: Option<String>,
+    /// How many there are. Counts written with a suffix, like `4G`, are
+    /// multiplied out.
+    pub count: u64,
+    /// The sockets the resource is close to, from `(S:0-1)`.
+    pub sockets: Vec<u32>,
+    /// The device indices in use, from `(IDX:0,2)`.
+    pub indices: Vec<u32>,
+}
+
+impl Gres {
+    /// Parse a comma separated GRES string. Empty strings and the `(null)`
+    /// and `N/A` Slurm writes for nodes without GRES have no entries.
+    pub fn parse_list(s: &str) -> Result<Vec<Gres>> {
+        let s = s.trim();
+        if matches!(s, "" | "(null)" | "N/A") {
+            return Ok(Vec::new());
+        }
+        let unbalanced = || SlurmError::Parse(format!("GRES list '{s}': unbalanced parentheses"));
+        let mut list = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.checked_sub(1).ok_or_else(unbalanced)?,
+                ',' if depth == 0 => {
+                    list.push(s[start..i].parse()?);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(unbalanced());
+        }
+        list.push(s[start..].parse()?);
+        Ok(list)
+    }
+
+    /// Whether this is the same resource and type as `other`.
+    pub fn same_kind(&self, other: &Gres) -> bool {
+        self.name == other.name && self.kind == other.kind
+    }
+}
+
+impl FromStr for Gres {
+    type Err = SlurmError;
+
+    /// Parse one GRES entry. A count that is left out is 1.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |reason: &str| SlurmError::Parse(format!("GRES '{s}': {reason}"));
+
+        let (spec, details) = match s.trim().split_once('(') {
+            Some((spec, details)) => {
+                let details = details
+                    .strip_suffix(')')
+                    .ok_or_else(|| error("unbalanced parentheses"))?;
+                (spec, Some(details))
+            }
+            None => (s.trim(), None),
+        };
+
+        let mut parts: Vec<&str> = spec.split(':').collect();
+        let name = parts.remove(0);
+        if name.is_empty() {
+            return Err(error("missing name"));
+        }
+        let count = match parts.last().and_then(|c| parse_count(c)) {
+            Some(count) => {
+                parts.pop();
+                count
+            }
+            None => 1,
+        };
+        // Flags like `no_consume` may sit between the type and count.
+        let kind = parts
+            .into_iter()
+            .find(|p| !p.is_empty() && *p != "no_consume");
+
+        let mut gres = Gres {
+            name: name.to_string(),
+            kind: kind.map(str::to_string),
+            count,
+            ..Gres::default()
+        };
+        if let Some(details) = details {
+            let (key, list) = details
+                .split_once(':')
+                .ok_or_else(|| error("missing ':' in parentheses"))?;
+            let list = parse_indices(list).ok_or_else(|| error("invalid index list"))?;
+            match key {
+                "S" => gres.sockets = list,
+                "IDX" => gres.indices = list,
+                _ => {}
+            }
+        }
+        Ok(gres)
+    }
+}
+
+/// Writes the entry the way Slurm does, like `gpu:a100:2(IDX:0,2)`.
+impl fmt::Display for Gres {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(kind) = &self.kind {
+            write!(f, ":{kind}")?;
+        }
+        write!(f, ":{}", self.count)?;
+        if !self.indices.is_empty() {
+            write!(f, "(IDX:{})", Indices(&self.indices))?;
+        } else if !self.sockets.is_empty() {
+            write!(f, "(S:{})", Indices(&self.sockets))?;
+        }
+        Ok(())
+    }
+}
+
+// A list of indices written with ranges, like `0-1,3`.
+struct Indices<'a>(&'a [u32]);
+
+impl fmt::Display for Indices<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.0;
+        while let Some((&start, _)) = rest.split_first() {
+            let len = rest
+                .iter()
+                .zip(start..)
+                .take_while(|(i, n)| **i == *n)
+                .count();
+            if rest.len() < self.0.len() {
+                f.write_str(",")?;
+            }
+            match len {
+                1 => write!(f, "{start}")?,
+                _ => write!(f, "{start}-{}", rest[len - 1])?,
+            }
+            rest = &rest[len..];
+        }
+        Ok(())
+    }
+}
+
+// A count, which may have a suffix multiplying it by powers of 1024, like
+// `4` or `16G`.
+fn parse_count(count: &str) -> Option<u64> {
+    let (number, power) = match count.char_indices().last()? {
+        (i, 'K' | 'k') => (&count[..i], 1),
+        (i, 'M' | 'm') => (&count[..i], 2),
+        (i, 'G' | 'g') => (&count[..i], 3),
+        (i, 'T' | 't') => (&count[..i], 4),
+        (i, 'P' | 'p') => (&count[..i], 5),
+        _ => (count, 0),
+    };
+    if !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse::<u64>().ok()?.checked_mul(1024u64.pow(power))
+}
+
+// A list of indices like `0-1,3`. `N/A` is an empty list.
+fn parse_indices(list: &str) -> Option<Vec<u32>> {
+    if list == "N/A" {
+        return Some(Vec::new());
+    }
+    let mut indices = Vec::new();
+    for range in list.split(',') {
+        let (start, end) = range.split_once('-').unwrap_or((range, range));
+        let (start, end): (u32, u32) = (start.parse().ok()?, end.parse().ok()?);
+        if end < start || indices.len() as u64 + u64::from(end - start) >= MAX_INDICES {
+            return None;
+        }
+        indices.extend(start..=end);
+    }
+    Some(indices)
+}
+
+impl Node {
+    /// The generic resources the node has.
+    pub fn gres_list(&self) -> Result<Vec<Gres>> {
+        Gres::parse_list(self.gres.as_deref().unwrap_or_default())
+    }
+
+    /// The generic resources allocated to jobs on the node.
+    pub fn gres_used_list(&self) -> Result<Vec<Gres>> {
+        Gres::parse_list(self.gres_used.as_deref().unwrap_or_default())
+    }
+
+    /// The generic resources unavailable because the node is draining.
+    pub fn gres_drained_list(&self) -> Result<Vec<Gres>> {
+        Gres::parse_list(self.gres_drained.as_deref().unwrap_or_default())
+    }
+
+    /// The generic resources not allocated to any job, by name and type.
+    pub fn gres_free(&self) -> Result<Vec<Gres>> {
+        let used = self.gres_used_list()?;
+        let free = self.gres_list()?.into_iter().map(|gres| {
+            let in_use: u64 = used
+                .iter()
+                .filter(|u| u.same_kind(&gres))
+                .map(|u| u.count)
+                .sum();
+            Gres {
+                count: gres.count.saturating_sub(in_use),
+                ..gres
+            }
+        });
+        Ok(free.collect())
+    }
+}
+
+impl JobResponseProperties {
+    /// The generic resources allocated to the job on each of its nodes, in
+    /// the order of `nodes`, including the device indices it holds.
+    pub fn gres_allocated(&self) -> Result<Vec<Vec<Gres>>> {
+        self.gres_detail
+            .iter()
+            .map(|detail| Gres::parse_list(detail))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gres(s: &str) -> Gres {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn typed_entries_with_sockets_and_indices() {
+        let node = gres("gpu:a100:4(S:0-1)");
+        assert_eq!(
+            (node.name.as_str(), node.kind.as_deref()),
+            ("gpu", Some("a100"))
+        );
+        assert_eq!((node.count, node.sockets.as_slice()), (4, &[0, 1][..]));
+        assert_eq!(node.to_string(), "gpu:a100:4(S:0-1)");
+
+        let job = gres("gpu:a100:3(IDX:0,2-3)");
+        assert_eq!(job.indices, [0, 2, 3]);
+        assert_eq!(job.to_string(), "gpu:a100:3(IDX:0,2-3)");
+    }
+
+    #[test]
+    fn untyped_entries() {
+        assert_eq!(gres("gpu:2").kind, None);
+        assert_eq!(gres("gpu:2").count, 2);
+        // A count that is left out is one, and a last part that is not a
+        // count is the type.
+        assert_eq!(gres("gpu").to_string(), "gpu:1");
+        assert_eq!(gres("gpu:tesla").to_string(), "gpu:tesla:1");
+        assert_eq!(gres("mps:2K").count, 2048);
+    }
+
+    #[test]
+    fn no_consume_is_not_a_type() {
+        assert_eq!(gres("shard:no_consume:4"), gres("shard:4"));
+        assert_eq!(gres("gpu:a100:no_consume:2"), gres("gpu:a100:2"));
+    }
+
+    #[test]
+    fn lists() {
+        for empty in ["", "(null)", "N/A"] {
+            assert!(Gres::parse_list(empty).unwrap().is_empty(), "{empty}");
+        }
+        // Commas inside parentheses do not split entries.
+        let list = Gres::parse_list("gpu:a100:2(IDX:0,2),gpu:v100:1(IDX:N/A)").unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].indices, [0, 2]);
+        assert!(list[1].indices.is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        for s in [
+            "",
+            ":2",
+            "gpu:2(S:0",
+            "gpu:2(S0)",
+            "gpu:2(IDX:2-1)",
+            "gpu:2(IDX:x)",
+            "gpu:2(IDX:0-4294967295)",
+        ] {
+            assert!(s.parse::<Gres>().is_err(), "{s}");
+        }
+        for s in [
+            "gpu:1),gpu:2",
+            "gpu:1(S:0,gpu:2",
+            "gpu:1(S:0)),gpu:2(",
+            "gpu:1,",
+        ] {
+            assert!(Gres::parse_list(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn free_on_a_node() {
+        let node = Node {
+            gres: Some("gpu:a100:4(S:0-1),gpu:v100:2,gpu:2".to_string()),
+            gres_used: Some("gpu:a100:1(IDX:0),gpu:v100:0(IDX:N/A),gpu:2".to_string()),
+            ..Node::default()
+        };
+        let free: Vec<String> = node
+            .gres_free()
+            .unwrap()
+            .iter()
+            .map(Gres::to_string)
+            .collect();
+        assert_eq!(free, ["gpu:a100:3(S:0-1)", "gpu:v100:2", "gpu:0"]);
+    }
+
+    #[test]
+    fn allocated_to_a_job() {
+        let job = JobResponseProperties {
+            gres_detail: vec![
+                "gpu:a100:2(IDX:0-1)".to_string(),
+                "gpu:a100:1(IDX:3)".to_string(),
+            ],
+            ..JobResponseProperties::default()
+        };
+        let allocated = job.gres_allocated().unwrap();
+        assert_eq!(allocated[0][0].indices, [0, 1]);
+        assert_eq!(allocated[1][0].indices, [3]);
+    }
+}
@@ -0,0 +1,462 @@
+//! Slurm hostlist expressions, like `gpu[001-010,015],cpu-a[1-3]`.
+use crate::{JobResources, JobResponseProperties, Partition, Reservation, Result, SlurmError};
+use std::{collections::HashSet, fmt, str::FromStr};
+
+// slurmctld refuses single ranges with more hosts than this as well.
+const MAX_RANGE: u64 = 64 * 1024;
+
+// The most names a hostlist may expand to. Brackets in one name multiply,
+// so capping each range alone would still let `a[0-65535]b[0-65535]`
+// expand into billions of names.
+const MAX_HOSTS: usize = 1024 * 1024;
+
+/// A set of host names, parsed from or written as a Slurm hostlist
+/// expression.
+///
+/// Expressions may hold several comma separated ranges in one bracket,
+/// zero padded numbers and several brackets in one name, like
+/// `rack[1-2]-node[01-04]`. Names are kept in the order Slurm sorts them,
+/// by their text and then by the number they end with, so `node9` comes
+/// before `node10`, and each name is kept once.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Hostlist {
+    hosts: Vec<String>,
+}
+
+impl Hostlist {
+    /// An empty hostlist.
+    pub fn new() -> Self {
+        Hostlist::default()
+    }
+
+    /// The number of hosts.
+    pub fn len(&self) -> usize {
+        self.hosts.len()
+    }
+
+    /// Whether there are no hosts.
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+    }
+
+    /// Whether `host` is one of the hosts.
+    pub fn contains(&self, host: &str) -> bool {
+        self.position(host).is_ok()
+    }
+
+    /// The host names, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.hosts.iter().map(String::as_str)
+    }
+
+    /// Add a host. Returns whether it was not already there.
+    pub fn insert<S: Into<String>>(&mut self, host: S) -> bool {
+        let host = host.into();
+        match self.position(&host) {
+            Ok(_) => false,
+            Err(i) => {
+                self.hosts.insert(i, host);
+                true
+            }
+        }
+    }
+
+    /// Remove a host. Returns whether it was there.
+    pub fn remove(&mut self, host: &str) -> bool {
+        match self.position(host) {
+            Ok(i) => {
+                self.hosts.remove(i);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Hosts in either hostlist.
+    pub fn union(&self, other: &Hostlist) -> Hostlist {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    /// Hosts in both hostlists.
+    pub fn intersection(&self, other: &Hostlist) -> Hostlist {
+        let other: HashSet<&str> = other.iter().collect();
+        let hosts = self.iter().filter(|h| other.contains(h));
+        Hostlist {
+            hosts: hosts.map(str::to_string).collect(),
+        }
+    }
+
+    /// Hosts in this hostlist but not in `other`.
+    pub fn difference(&self, other: &Hostlist) -> Hostlist {
+        let other: HashSet<&str> = other.iter().collect();
+        let hosts = self.iter().filter(|h| !other.contains(h));
+        Hostlist {
+            hosts: hosts.map(str::to_string).collect(),
+        }
+    }
+
+    /// Whether every host is also in `other`.
+    pub fn is_subset(&self, other: &Hostlist) -> bool {
+        self.iter().all(|h| other.contains(h))
+    }
+
+    fn position(&self, host: &str) -> std::result::Result<usize, usize> {
+        self.hosts.binary_search_by(|h| key(h).cmp(&key(host)))
+    }
+}
+
+impl FromStr for Hostlist {
+    type Err = SlurmError;
+
+    /// Parse and expand a hostlist expression. An empty string is an empty
+    /// hostlist.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |reason: &str| SlurmError::Parse(format!("hostlist '{s}': {reason}"));
+
+        let mut hosts = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            match c {
+                '[' if depth == 0 => depth += 1,
+                ']' if depth == 1 => depth -= 1,
+                '[' | ']' => return Err(error("unbalanced brackets")),
+                ',' | ' ' | '\t' | '\n' if depth == 0 => {
+                    expand(&s[start..i], &mut hosts).map_err(error)?;
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(error("unbalanced brackets"));
+        }
+        expand(&s[start..], &mut hosts).map_err(error)?;
+        Ok(hosts.into_iter().collect())
+    }
+}
+
+/// Writes the hosts as a compressed hostlist expression, the way Slurm
+/// does: consecutive numbers that share a prefix and padding become a
+/// range, like `gpu[001-010,015]`. Only the number a name ends with is
+/// compressed, so names with several numbers may take more than one entry.
+impl fmt::Display for Hostlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<(&str, Vec<Range>)> = Vec::new();
+        for host in &self.hosts {
+            let (prefix, number) = split(host);
+            let Some((digits, value)) = number else {
+                entries.push((host, Vec::new()));
+                continue;
+            };
+            if let Some((last, ranges)) = entries.last_mut() {
+                if *last == prefix && !ranges.is_empty() {
+                    let range = ranges.last_mut().expect("ranges is not empty");
+                    if value == range.hi + 1 && format!("{value:0w$}", w = range.width) == digits {
+                        range.hi = value;
+                    } else {
+                        ranges.push(Range::new(digits, value));
+                    }
+                    continue;
+                }
+            }
+            entries.push((prefix, vec![Range::new(digits, value)]));
+        }
+
+        for (i, (prefix, ranges)) in entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(prefix)?;
+            match ranges.as_slice() {
+                [] => {}
+                [range] if range.lo == range.hi => write!(f, "{range}")?,
+                ranges => {
+                    f.write_str("[")?;
+                    for (j, range) in ranges.iter().enumerate() {
+                        if j > 0 {
+                            f.write_str(",")?;
+                        }
+                        write!(f, "{range}")?;
+                    }
+                    f.write_str("]")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Hostlist {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut hosts: Vec<String> = iter.into_iter().map(Into::into).collect();
+        hosts.sort_by(|a, b| key(a).cmp(&key(b)));
+        hosts.dedup();
+        Hostlist { hosts }
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Hostlist {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for host in iter {
+            self.insert(host);
+        }
+    }
+}
+
+impl IntoIterator for Hostlist {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.hosts.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Hostlist {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.hosts.iter()
+    }
+}
+
+// Numbers from `lo` to `hi`, padded with zeros to `width` digits.
+struct Range {
+    lo: u64,
+    hi: u64,
+    width: usize,
+}
+
+impl Range {
+    fn new(digits: &str, value: u64) -> Self {
+        Range {
+            lo: value,
+            hi: value,
+            width: digits.len(),
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width;
+        if self.lo == self.hi {
+            write!(f, "{:0width$}", self.lo)
+        } else {
+            write!(f, "{:0width$}-{:0width$}", self.lo, self.hi)
+        }
+    }
+}
+
+// Split a host name into the text before the number it ends with, and
+// that number, both as written and as a value.
+fn split(host: &str) -> (&str, Option<(&str, u64)>) {
+    let prefix = host.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &host[prefix.len()..];
+    match digits.parse() {
+        Ok(value) => (prefix, Some((digits, value))),
+        Err(_) => (host, None),
+    }
+}
+
+// Where a host name goes in the order Slurm sorts names in.
+fn key(host: &str) -> (&str, Option<(u64, usize)>) {
+    let (prefix, number) = split(host);
+    (prefix, number.map(|(digits, value)| (value, digits.len())))
+}
+
+// Expand one entry of a hostlist, like `rack[1-2]-node[01-04]`, into
+// `hosts`. Each bracket multiplies the names made so far.
+fn expand(entry: &str, hosts: &mut Vec<String>) -> std::result::Result<(), &'static str> {
+    if entry.is_empty() {
+        return Ok(());
+    }
+    let mut names = vec![String::new()];
+    let mut rest = entry;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('[') else {
+            names.iter_mut().for_each(|n| n.push_str(rest));
+            break;
+        };
+        let close = rest.find(']').ok_or("unbalanced brackets")?;
+        let literal = &rest[..open];
+        let numbers = numbers(&rest[open + 1..close])?;
+        names
+            .len()
+            .checked_mul(numbers.len())
+            .filter(|count| hosts.len() + count <= MAX_HOSTS)
+            .ok_or("too many hosts")?;
+        names = names
+            .iter()
+            .flat_map(|name| numbers.iter().map(move |n| format!("{name}{literal}{n}")))
+            .collect();
+        rest = &rest[close + 1..];
+    }
+    hosts.extend(names);
+    Ok(())
+}
+
+// The numbers in a bracket, like `001-010,015`, as they are written in
+// host names.
+fn numbers(ranges: &str) -> std::result::Result<Vec<String>, &'static str> {
+    let mut numbers = Vec::new();
+    for range in ranges.split(',') {
+        let (lo, hi) = range.split_once('-').unwrap_or((range, range));
+        let parse = |n: &str| match n.bytes().all(|b| b.is_ascii_digit()) {
+            true => n.parse::<u64>().map_err(|_| "invalid range"),
+            false => Err("invalid range"),
+        };
+        let (start, end) = (parse(lo)?, parse(hi)?);
+        if end < start {
+            return Err("range ends before it starts");
+        }
+        if end - start >= MAX_RANGE {
+            return Err("range is too large");
+        }
+        if numbers.len() as u64 + (end - start) >= MAX_HOSTS as u64 {
+            return Err("too many hosts");
+        }
+        let width = lo.len();
+        numbers.extend((start..=end).map(|n| format!("{n:0width$}")));
+    }
+    Ok(numbers)
+}
+
+impl Partition {
+    /// The nodes in the partition.
+    pub fn node_hostlist(&self) -> Result<Hostlist> {
+        parse(&self.nodes)
+    }
+}
+
+impl Reservation {
+    /// The nodes in the reservation.
+    pub fn node_hostlist(&self) -> Result<Hostlist> {
+        parse(&self.node_list)
+    }
+}
+
+impl JobResponseProperties {
+    /// The nodes allocated to the job.
+    pub fn node_hostlist(&self) -> Result<Hostlist> {
+        parse(&self.nodes)
+    }
+
+    /// The nodes the job asked to run on.
+    pub fn required_node_hostlist(&self) -> Result<Hostlist> {
+        parse(&self.required_nodes)
+    }
+
+    /// The nodes the job asked not to run on.
+    pub fn excluded_node_hostlist(&self) -> Result<Hostlist> {
+        parse(&self.excluded_nodes)
+    }
+}
+
+impl JobResources {
+    /// The nodes allocated to the job.
+    pub fn node_hostlist(&self) -> Result<Hostlist> {
+        parse(&self.nodes)
+    }
+}
+
+fn parse(field: &Option<String>) -> Result<Hostlist> {
+    field.as_deref().unwrap_or_default().parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hosts(hostlist: &Hostlist) -> Vec<&str> {
+        hostlist.iter().collect()
+    }
+
+    #[test]
+    fn keeps_zero_padding_per_range() {
+        let hostlist: Hostlist = "node[1,01-02]".parse().unwrap();
+        assert_eq!(hosts(&hostlist), ["node1", "node01", "node02"]);
+        assert_eq!(hostlist.to_string(), "node[1,01-02]");
+
+        let hostlist: Hostlist = "gpu[098-101]".parse().unwrap();
+        assert_eq!(hosts(&hostlist), ["gpu098", "gpu099", "gpu100", "gpu101"]);
+        assert_eq!(hostlist.to_string(), "gpu[098-101]");
+    }
+
+    #[test]
+    fn sorts_by_number_not_text() {
+        let hostlist: Hostlist = "node[9-10]".parse().unwrap();
+        assert_eq!(hosts(&hostlist), ["node9", "node10"]);
+        assert_eq!(hostlist.to_string(), "node[9-10]");
+
+        let hostlist: Hostlist = "n10 n9,n1".parse().unwrap();
+        assert_eq!(hostlist.to_string(), "n[1,9-10]");
+    }
+
+    #[test]
+    fn brackets_multiply() {
+        let hostlist: Hostlist = "rack[1-2]-node[01-02]".parse().unwrap();
+        assert_eq!(
+            hosts(&hostlist),
+            [
+                "rack1-node01",
+                "rack1-node02",
+                "rack2-node01",
+                "rack2-node02"
+            ]
+        );
+        // Only the trailing number is compressed.
+        assert_eq!(hostlist.to_string(), "rack1-node[01-02],rack2-node[01-02]");
+        assert_eq!(hostlist.to_string().parse::<Hostlist>().unwrap(), hostlist);
+    }
+
+    #[test]
+    fn names_without_numbers_and_duplicates() {
+        let hostlist: Hostlist = "login,node[1-2],node2,login".parse().unwrap();
+        assert_eq!(hosts(&hostlist), ["login", "node1", "node2"]);
+        assert_eq!(hostlist.to_string(), "login,node[1-2]");
+        assert!("".parse::<Hostlist>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_expressions() {
+        for expression in [
+            "node[1-",
+            "node]1",
+            "node[[1]]",
+            "node[3-1]",
+            "node[a-b]",
+            "node[+1]",
+            "node[0-65536]",
+        ] {
+            assert!(expression.parse::<Hostlist>().is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: Hostlist = "node[1-4]".parse().unwrap();
+        let b: Hostlist = "node[3-6]".parse().unwrap();
+        assert_eq!(a.union(&b).to_string(), "node[1-6]");
+        assert_eq!(a.intersection(&b).to_string(), "node[3-4]");
+        assert_eq!(a.difference(&b).to_string(), "node[1-2]");
+        assert!(a.intersection(&b).is_subset(&b));
+        assert!(!a.is_subset(&b));
+
+        let mut c = a.clone();
+        assert!(c.insert("node10"));
+        assert!(!c.insert("node1"));
+        assert!(c.remove("node2"));
+        assert!(!c.contains("node2"));
+        assert_eq!(c.to_string(), "node[1,3-4,10]");
+    }
+
+    #[test]
+    fn caps_names_across_brackets() {
+        assert!("a[0-65535]b[0-65535]".parse::<Hostlist>().is_err());
+        assert!("a[0-1023]b[0-1024]".parse::<Hostlist>().is_err());
+        assert!("a[0-1023],b[0-1023]".parse::<Hostlist>().is_ok());
+    }
+}
@@ -0,0 +1,386 @@
+//! Typed job states and the reasons Slurm gives for them.
+use crate::{JobResponseProperties, Result, SlurmError};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+/// The base state of a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JobState {
+    Pending,
+    Running,
+    Suspended,
+    Completed,
+    Cancelled,
+    Failed,
+    Timeout,
+    NodeFail,
+    Preempted,
+    BootFail,
+    Deadline,
+    OutOfMemory,
+}
+
+impl JobState {
+    /// Every base state.
+    pub const ALL: [JobState; 12] = [
+        JobState::Pending,
+        JobState::Running,
+        JobState::Suspended,
+        JobState::Completed,
+        JobState::Cancelled,
+        JobState::Failed,
+        JobState::Timeout,
+        JobState::NodeFail,
+        JobState::Preempted,
+        JobState::BootFail,
+        JobState::Deadline,
+        JobState::OutOfMemory,
+    ];
+
+    /// The state as Slurm writes it, e.g. `NODE_FAIL`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobState::Pending => "PENDING",
+            JobState::Running => "RUNNING",
+            JobState::Suspended => "SUSPENDED",
+            JobState::Completed => "COMPLETED",
+            JobState::Cancelled => "CANCELLED",
+            JobState::Failed => "FAILED",
+            JobState::Timeout => "TIMEOUT",
+            JobState::NodeFail => "NODE_FAIL",
+            JobState::Preempted => "PREEMPTED",
+            JobState::BootFail => "BOOT_FAIL",
+            JobState::Deadline => "DEADLINE",
+            JobState::OutOfMemory => "OUT_OF_MEMORY",
+        }
+    }
+
+    /// Whether the job has ended, successfully or not. It may still be
+    /// `COMPLETING` while its nodes clean up.
+    pub fn is_terminal(&self) -> bool {
+        !self.is_active()
+    }
+
+    /// Whether the job is waiting to run, running or suspended.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            JobState::Pending | JobState::Running | JobState::Suspended
+        )
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobState {
+    type Err = SlurmError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        JobState::ALL
+            .into_iter()
+            .find(|state| state.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| SlurmError::Parse(format!("job state '{s}'")))
+    }
+}
+
+// An enum of the strings Slurm uses for something, with a variant for
+// strings this crate does not know yet.
+macro_rules! string_enum {
+    ($(#[$meta:meta])* $name:ident { $($(#[$vmeta:meta])* $variant:ident => $string:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+            /// A value this crate does not know, as Slurm wrote it.
+            Other(String),
+        }
+
+        impl $name {
+            /// The value as Slurm writes it.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $string,)*
+                    $name::Other(s) => s,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                match s {
+                    $($string => $name::$variant,)*
+                    _ => $name::Other(s.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        // Written as the string Slurm uses, `Other` included.
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                Ok($name::from(s.as_str()))
+            }
+        }
+
+        impl JsonSchema for $name {
+            fn schema_name() -> String {
+                stringify!($name).to_string()
+            }
+
+            fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                String::json_schema(gen)
+            }
+        }
+    };
+}
+
+string_enum! {
+    /// Flags Slurm sets on a job alongside its base state.
+    JobStateFlag {
+        /// The job has ended and its nodes are still cleaning up.
+        Completing => "COMPLETING",
+        /// The job's nodes are booting.
+        Configuring => "CONFIGURING",
+        LaunchFailed => "LAUNCH_FAILED",
+        PowerUpNode => "POWER_UP_NODE",
+        ReconfigFail => "RECONFIG_FAIL",
+        /// The job is being requeued.
+        Requeued => "REQUEUED",
+        RequeueFed => "REQUEUE_FED",
+        RequeueHold => "REQUEUE_HOLD",
+        /// The job's size is changing.
+        Resizing => "RESIZING",
+        ResvDelHold => "RESV_DEL_HOLD",
+        Revoked => "REVOKED",
+        Signaling => "SIGNALING",
+        SpecialExit => "SPECIAL_EXIT",
+        StageOut => "STAGE_OUT",
+        Stopped => "STOPPED",
+        UpdateDb => "UPDATE_DB",
+    }
+}
+
+string_enum! {
+    /// Why a job is in its state, mostly why a pending job is not running.
+    JobStateReason {
+        /// Slurm has no reason to give.
+        NoReason => "None",
+        AssocGrpCpuLimit => "AssocGrpCpuLimit",
+        AssocGrpCpuMinutesLimit => "AssocGrpCPUMinutesLimit",
+        AssocGrpGres => "AssocGrpGRES",
+        AssocGrpJobsLimit => "AssocGrpJobsLimit",
+        AssocGrpMemLimit => "AssocGrpMemLimit",
+        AssocGrpNodeLimit => "AssocGrpNodeLimit",
+        AssocGrpSubmitJobsLimit => "AssocGrpSubmitJobsLimit",
+        AssocJobLimit => "AssocJobLimit",
+        AssocMaxJobsLimit => "AssocMaxJobsLimit",
+        AssocMaxSubmitJobLimit => "AssocMaxSubmitJobLimit",
+        AssocMaxWallDurationPerJobLimit => "AssocMaxWallDurationPerJobLimit",
+        AssocResourceLimit => "AssocResourceLimit",
+        AssocTimeLimit => "AssocTimeLimit",
+        BadConstraints => "BadConstraints",
+        BeginTime => "BeginTime",
+        BurstBufferResources => "BurstBufferResources",
+        BurstBufferStageIn => "BurstBufferStageIn",
+        Cleaning => "Cleaning",
+        DeadLine => "DeadLine",
+        Dependency => "Dependency",
+        DependencyNeverSatisfied => "DependencyNeverSatisfied",
+        FrontEndDown => "FrontEndDown",
+        InactiveLimit => "InactiveLimit",
+        InvalidAccount => "InvalidAccount",
+        InvalidQos => "InvalidQOS",
+        JobArrayTaskLimit => "JobArrayTaskLimit",
+        JobHeldAdmin => "JobHeldAdmin",
+        JobHeldUser => "JobHeldUser",
+        JobLaunchFailure => "JobLaunchFailure",
+        Licenses => "Licenses",
+        NodeDown => "NodeDown",
+        NonZeroExitCode => "NonZeroExitCode",
+        OutOfMemory => "OutOfMemory",
+        PartitionConfig => "PartitionConfig",
+        PartitionDown => "PartitionDown",
+        PartitionInactive => "PartitionInactive",
+        PartitionNodeLimit => "PartitionNodeLimit",
+        PartitionTimeLimit => "PartitionTimeLimit",
+        PowerUpNode => "PowerUpNode",
+        Priority => "Priority",
+        Prolog => "Prolog",
+        QosGrpCpuLimit => "QOSGrpCpuLimit",
+        QosGrpCpuMinutesLimit => "QOSGrpCPUMinutesLimit",
+        QosGrpGres => "QOSGrpGRES",
+        QosGrpJobsLimit => "QOSGrpJobsLimit",
+        QosGrpMemLimit => "QOSGrpMemLimit",
+        QosGrpNodeLimit => "QOSGrpNodeLimit",
+        QosJobLimit => "QOSJobLimit",
+        QosMaxCpuPerUserLimit => "QOSMaxCpuPerUserLimit",
+        QosMaxGresPerUser => "QOSMaxGRESPerUser",
+        QosMaxJobsPerUserLimit => "QOSMaxJobsPerUserLimit",
+        QosMaxNodePerUserLimit => "QOSMaxNodePerUserLimit",
+        QosMaxSubmitJobPerUserLimit => "QOSMaxSubmitJobPerUserLimit",
+        QosMaxWallDurationPerJobLimit => "QOSMaxWallDurationPerJobLimit",
+        QosResourceLimit => "QOSResourceLimit",
+        QosTimeLimit => "QOSTimeLimit",
+        QosUsageThreshold => "QOSUsageThreshold",
+        RaisedSignal => "RaisedSignal",
+        ReqNodeNotAvail => "ReqNodeNotAvail",
+        Reservation => "Reservation",
+        ReservationDeleted => "ReservationDeleted",
+        Resources => "Resources",
+        SystemFailure => "SystemFailure",
+        TimeLimit => "TimeLimit",
+    }
+}
+
+impl JobResponseProperties {
+    /// The job's base state. `None` if it is unknown, or if an older API
+    /// version reported only a flag like `COMPLETING` in its place.
+    pub fn state(&self) -> Option<JobState> {
+        self.job_state.as_deref()?.parse().ok()
+    }
+
+    /// The flags set on the job's state, like `COMPLETING`.
+    pub fn state_flags(&self) -> Vec<JobStateFlag> {
+        // Before v0.0.40 a set flag replaced the base state.
+        let in_state = self.job_state.as_deref().filter(|_| self.state().is_none());
+        in_state
+            .into_iter()
+            .chain(self.job_state_flags.iter().map(String::as_str))
+            .map(JobStateFlag::from)
+            .collect()
+    }
+
+    /// Whether the job has ended and its nodes are done cleaning up.
+    pub fn is_terminal(&self) -> bool {
+        self.state().is_some_and(|state| state.is_terminal())
+            && !self.state_flags().contains(&JobStateFlag::Completing)
+    }
+
+    /// Whether the job is pending, running, suspended or still completing.
+    pub fn is_active(&self) -> bool {
+        self.state().is_some_and(|state| state.is_active())
+            || self.state_flags().contains(&JobStateFlag::Completing)
+    }
+
+    /// Why the job is in its state, from `state_reason`.
+    pub fn reason(&self) -> Option<JobStateReason> {
+        self.state_reason.as_deref().map(JobStateReason::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(state: &str, flags: &[&str]) -> JobResponseProperties {
+        JobResponseProperties {
+            job_state: Some(state.to_string()),
+            job_state_flags: flags.iter().map(|f| f.to_string()).collect(),
+            ..JobResponseProperties::default()
+        }
+    }
+
+    #[test]
+    fn states_parse_ignoring_case() {
+        for state in JobState::ALL {
+            assert_eq!(state.to_string().parse::<JobState>().unwrap(), state);
+            assert_eq!(
+                state.as_str().to_lowercase().parse::<JobState>().unwrap(),
+                state
+            );
+        }
+        assert!("COMPLETING".parse::<JobState>().is_err());
+    }
+
+    #[test]
+    fn completing_before_v0_0_40() {
+        // Older versions put the flag where the base state goes.
+        let job = job("COMPLETING", &[]);
+        assert_eq!(job.state(), None);
+        assert_eq!(job.state_flags(), [JobStateFlag::Completing]);
+        assert!(job.is_active());
+        assert!(!job.is_terminal());
+    }
+
+    #[test]
+    fn completing_from_v0_0_40() {
+        let job = job("COMPLETED", &["COMPLETING"]);
+        assert_eq!(job.state(), Some(JobState::Completed));
+        assert_eq!(job.state_flags(), [JobStateFlag::Completing]);
+        assert!(job.is_active());
+        assert!(!job.is_terminal());
+    }
+
+    #[test]
+    fn terminal_and_active() {
+        assert!(job("FAILED", &["NEW_FLAG"]).is_terminal());
+        assert!(job("PENDING", &["REQUEUED"]).is_active());
+        assert!(job("SUSPENDED", &[]).is_active());
+        let unknown = JobResponseProperties::default();
+        assert!(!unknown.is_active() && !unknown.is_terminal());
+    }
+
+    #[test]
+    fn unknown_flags_and_reasons_are_kept() {
+        let flag = JobStateFlag::from("NEW_FLAG");
+        assert_eq!(flag, JobStateFlag::Other("NEW_FLAG".to_string()));
+        assert_eq!(flag.to_string(), "NEW_FLAG");
+
+        // Reasons keep Slurm's inconsistent casing.
+        assert_eq!(job("PENDING", &[]).reason(), None);
+        assert_eq!(
+            JobStateReason::from("QOSGrpCpuLimit"),
+            JobStateReason::QosGrpCpuLimit
+        );
+        assert_eq!(
+            JobStateReason::from("AssocGrpCPUMinutesLimit"),
+            JobStateReason::AssocGrpCpuMinutesLimit
+        );
+        assert_eq!(JobStateReason::from("NewReason").to_string(), "NewReason");
+        assert_eq!(JobStateReason::from("None"), JobStateReason::NoReason);
+        assert_eq!(JobStateReason::NoReason.as_str(), "None");
+    }
+
+    #[test]
+    fn serde_uses_slurm_strings() {
+        let json = serde_json::to_string(&JobState::NodeFail).unwrap();
+        assert_eq!(json, r#""NODE_FAIL""#);
+        assert_eq!(
+            serde_json::from_str::<JobState>(&json).unwrap(),
+            JobState::NodeFail
+        );
+
+        let reasons = [
+            JobStateReason::NoReason,
+            JobStateReason::QosGrpCpuLimit,
+            JobStateReason::Other("NewReason".to_string()),
+        ];
+        let json = serde_json::to_string(&reasons).unwrap();
+        assert_eq!(json, r#"["None","QOSGrpCpuLimit","NewReason"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<JobStateReason>>(&json).unwrap(),
+            reasons
+        );
+
+        let flag: JobStateFlag = serde_json::from_str(r#""COMPLETING""#).unwrap();
+        assert_eq!(flag, JobStateFlag::Completing);
+    }
+}
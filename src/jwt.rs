@@ -0,0 +1,150 @@
+//! Minting `auth/jwt` tokens from the cluster's HS256 key.
+use crate::{CredentialProvider, Credentials, Result, SlurmError};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{
+    fmt, fs,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Lifespan of minted tokens unless set with `Jwt::lifespan`. This matches
+/// the default of `scontrol token`.
+const DEFAULT_LIFESPAN: Duration = Duration::from_secs(1800);
+
+// A new token is fetched once the current one has less than this left, so
+// a request never leaves with a token that expires on the way.
+pub(crate) const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Signs HS256 tokens for slurmrestd, like `scontrol token` does, from the
+/// `jwt_hs256.key` slurmctld and slurmdbd are configured with. Tokens are
+/// reused until shortly before they expire and then minted again, so a
+/// long-lived client never sends an expired token. Requests slurmrestd
+/// rejects with a 401 are not retried, as a new token signed with the same
+/// key would be rejected too.
+///
+/// Anyone holding the key can act as any user, root included, so only
+/// use this from services that are trusted with it.
+pub struct Jwt {
+    key: Vec<u8>,
+    username: String,
+    lifespan: Duration,
+    // The current token and when it expires, in seconds since the epoch.
+    current: Mutex<Option<(String, u64)>>,
+}
+
+impl Jwt {
+    /// Sign tokens for `username` with `key`, the contents of
+    /// `jwt_hs256.key`.
+    pub fn new<K, U>(key: K, username: U) -> Result<Self>
+    where
+        K: Into<Vec<u8>>,
+        U: ToString,
+    {
+        let key = key.into();
+        if key.is_empty() {
+            return Err(SlurmError::Config("JWT key is empty".into()));
+        }
+        Ok(Jwt {
+            key,
+            username: username.to_string(),
+            lifespan: DEFAULT_LIFESPAN,
+            current: Mutex::new(None),
+        })
+    }
+
+    /// Sign tokens for `username` with the key read from `path`, e.g.
+    /// `/var/spool/slurm/statesave/jwt_hs256.key`.
+    pub fn from_key_file<P, U>(path: P, username: U) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        U: ToString,
+    {
+        let path = path.as_ref();
+        let key = fs::read(path)
+            .map_err(|e| SlurmError::Config(format!("reading JWT key {}: {e}", path.display())))?;
+        Jwt::new(key, username)
+    }
+
+    /// How long each minted token is valid for. Defaults to 30 minutes.
+    pub fn lifespan(mut self, lifespan: Duration) -> Self {
+        self.lifespan = lifespan;
+        self
+    }
+
+    /// User the tokens are signed for, sent as the token's `sun` claim.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// A token that is valid for at least another minute, or for half its
+    /// lifespan if that is shorter than two minutes.
+    pub fn token(&self) -> Result<String> {
+        let now = now()?;
+        let margin = REFRESH_MARGIN.min(self.lifespan / 2).as_secs();
+
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((token, exp)) = current.as_ref() {
+            if now + margin < *exp {
+                return Ok(token.clone());
+            }
+        }
+        let exp = now + self.lifespan.as_secs();
+        let token = self.sign(now, exp);
+        *current = Some((token.clone(), exp));
+        Ok(token)
+    }
+
+    fn sign(&self, iat: u64, exp: u64) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let claims = serde_json::json!({
+            "iat": iat,
+            "exp": exp,
+            "sun": self.username,
+        });
+        let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let message = format!("{header}.{claims}");
+
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(message.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{message}.{signature}")
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for Jwt {
+    async fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::new(&self.username, self.token()?))
+    }
+}
+
+// Keep the key out of logs.
+impl fmt::Debug for Jwt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Jwt")
+            .field("username", &self.username)
+            .field("lifespan", &self.lifespan)
+            .finish_non_exhaustive()
+    }
+}
+
+// Seconds since the epoch.
+pub(crate) fn now() -> Result<u64> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .map_err(|_| SlurmError::Config("system clock is before 1970".into()))
+}
+
+// The `exp` claim of a token, without checking its signature.
+pub(crate) fn expiry(token: &str) -> Option<u64> {
+    let claims = token.split('.').nth(1)?;
+    let claims = URL_SAFE_NO_PAD.decode(claims).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&claims).ok()?;
+    claims.get("exp")?.as_u64()
+}
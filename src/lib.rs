@@ -2,61 +2,197 @@
 //!
 //! For more information, the Slurm REST API is documented at
 //! <https://slurm.schedmd.com/rest_api.html>
-use anyhow::{bail, Result};
-use reqwest::{header, Client, Method, Request, StatusCode, Url};
+use reqwest::{Method, Request};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use std::{env, sync::Arc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+mod billing;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+mod cache;
+mod credentials;
+mod discovery;
+mod errno;
+mod error;
+mod gres;
+mod hostlist;
+mod job_state;
+mod jwt;
+mod models;
+mod normalize;
+mod retry;
+mod throttle;
+mod tracker;
+mod transport;
+mod tres;
+#[cfg(unix)]
+mod unix;
+mod version;
+pub use billing::{BillingMode, BillingWeights};
+pub use builder::{SlurmBuilder, SlurmDBBuilder};
+pub use cache::{CacheResource, CachedSlurm};
+pub use credentials::{
+    CommandCredentials, CredentialProvider, Credentials, EnvCredentials, FileCredentials,
+};
+pub use error::{Result, SlurmError};
+pub use gres::Gres;
+pub use hostlist::Hostlist;
+pub use job_state::{JobState, JobStateFlag, JobStateReason};
+pub use jwt::Jwt;
+pub use models::*;
+pub use retry::RetryPolicy;
+pub use tracker::UpdateTracker;
+use transport::Transport;
+pub use tres::{Tres, TresKey};
+pub use version::{ApiVersion, INFINITE, INFINITE16, INFINITE64};
+
+/// A connection to slurmrestd shared by `Slurm` and `SlurmDB` handles.
+/// The HTTP client, its connection pool and the credentials are set up
+/// once here, and every handle derived from it reuses them.
+#[derive(Clone)]
+pub struct SlurmClient {
+    transport: Arc<Transport>,
+    error_policy: ErrorPolicy,
+}
+
+impl SlurmClient {
+    /// Start building a new shared client.
+    pub fn builder() -> SlurmBuilder<SlurmClient> {
+        SlurmBuilder::new()
+    }
+
+    /// Handle for the slurmctld half of the API.
+    pub fn slurm(&self) -> Slurm {
+        Slurm {
+            transport: self.transport.clone(),
+            error_policy: self.error_policy,
+        }
+    }
+
+    /// Handle for the slurmdbd half of the API.
+    pub fn slurmdb(&self) -> SlurmDB {
+        SlurmDB {
+            transport: self.transport.clone(),
+            error_policy: self.error_policy,
+        }
+    }
 
-const SLURM_USER: &str = "X-SLURM-USER-NAME";
-const SLURM_TOKEN: &str = "X-SLURM-USER-TOKEN";
-const SLURM_API_VERSION: &str = "v0.0.38";
+    /// Version of the REST API requests are sent to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.transport.version
+    }
+}
 
 /// Entrypoint for interacting with the API.
 /// To authenticate with the API, we need a user and a token.
+#[derive(Clone)]
 pub struct Slurm {
-    user: String,
-    token: String,
-    endpoint: Url,
-    client: Arc<Client>,
+    transport: Arc<Transport>,
+    error_policy: ErrorPolicy,
 }
 
 impl Slurm {
+    /// Start building a new Slurm client. Unlike `new` and `new_from_env`,
+    /// the builder reports problems as errors instead of panicking.
+    pub fn builder() -> SlurmBuilder<Slurm> {
+        SlurmBuilder::new()
+    }
+
     /// Create a new Slurm client struct. It takes any type that can convert
     /// into a &str and any type that can convert into a URL for the endpoint.
     /// Since this lib is useless withouth a client to connect with, this
-    /// will panic if creating a client fails.
+    /// will panic if creating a client fails. Use `Slurm::builder()` to
+    /// handle that error instead.
     pub fn new<U, T, L>(user: U, token: T, url: L) -> Self
     where
         U: ToString,
         T: ToString,
         L: ToString,
     {
-        let client = Client::builder().build();
-        match client {
-            Ok(c) => Slurm {
-                user: user.to_string(),
-                token: token.to_string(),
-                endpoint: Url::parse(&url.to_string()).expect("Unable to parse endpoint into URL!"),
-                client: Arc::new(c),
-            },
-            Err(e) => panic!("Unable to create client: {e:?}"),
-        }
+        Slurm::builder()
+            .user(user)
+            .token(token)
+            .endpoint(url)
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
     }
 
     /// Create a new Slurm client struct from environment variables.
-    /// It takes any type that can convert into a &str.
     /// Since this lib is useless withouth a client to connect with, this
-    /// will panic if creating a client fails.
+    /// will panic if a variable is missing or creating a client fails.
+    /// Use `Slurm::builder().load_env()` to handle that error instead.
     pub fn new_from_env() -> Self {
-        let endpoint =
-            env::var("X_SLURM_ENDPOINT").expect("env variable 'X_SLURM_ENDPOINT' should be set");
-        let user =
-            env::var("X_SLURM_USER_NAME").expect("env variable 'X_SLURM_USER_NAME' should be set");
-        let token = env::var("X_SLURM_USER_TOKEN")
-            .expect("env variable 'X_SLURM_USER_TOKEN' should be set");
+        Slurm::builder()
+            .load_env()
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
+    }
+
+    /// Create a client for a local slurmrestd listening on a unix socket
+    /// with `auth/local`, e.g. `Slurm::unix("/run/slurmrestd.sock")`. No
+    /// user name or token is sent; slurmrestd authenticates the process
+    /// connecting to the socket.
+    #[cfg(unix)]
+    pub fn unix<P: Into<std::path::PathBuf>>(path: P) -> Result<Self> {
+        Slurm::builder().unix_socket(path).local_auth().build()
+    }
+
+    /// Set how errors Slurm reports in an otherwise successful response
+    /// are handled. Defaults to `ErrorPolicy::Strict`.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// The shared client this handle was derived from.
+    pub fn client(&self) -> SlurmClient {
+        SlurmClient {
+            transport: self.transport.clone(),
+            error_policy: self.error_policy,
+        }
+    }
+
+    /// Version of the REST API requests are sent to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.transport.version
+    }
 
-        Slurm::new(user, token, endpoint)
+    // Send a request and parse the response, applying our error policy.
+    async fn execute<T>(&self, request: Request) -> Result<T>
+    where
+        T: DeserializeOwned + SlurmResponse,
+    {
+        let r: T = self.transport.execute("slurm", request).await?;
+        self.error_policy.check(r)
+    }
+
+    // GET `path` only if it changed since `update_time`. slurmctld
+    // reports that nothing changed with `SLURM_NO_CHANGE_IN_DATA`, and
+    // from v0.0.40 on also sends when the data last changed.
+    async fn get_since<T>(
+        &self,
+        path: &str,
+        update_time: i64,
+        last_update: fn(&T) -> Option<i64>,
+    ) -> Result<Conditional<T>>
+    where
+        T: DeserializeOwned + SlurmResponse,
+    {
+        let query = vec![("update_time", update_time.to_string())];
+        let request = self.request(Method::GET, path, (), Some(query))?;
+        let mut response: T = self.transport.execute("slurm", request).await?;
+
+        // Not an error for the caller, so keep it from `ErrorPolicy`.
+        let errors = response.errors_mut();
+        let reported = errors.len();
+        errors.retain(|e| e.error_number != errno::SLURM_NO_CHANGE_IN_DATA);
+        let no_change = errors.len() < reported;
+
+        let response = self.error_policy.check(response)?;
+        let unchanged = no_change || last_update(&response).is_some_and(|t| t <= update_time);
+        Ok(Conditional::new(response, unchanged))
     }
 
     // This will be our internal request builder.
@@ -70,281 +206,246 @@ impl Slurm {
     where
         B: Serialize,
     {
-        // https://slurm-endpoint/{slurm,slurmdb}/v0.0.38/{nodes, diag, etc..}
-        let url_path = format!("slurm/{}/{}", SLURM_API_VERSION, path);
-        let url = self.endpoint.join(&url_path)?;
-
-        // Build auth headers
-        let user_header_name =
-            header::HeaderName::from_bytes(SLURM_USER.to_lowercase().as_bytes())?;
-        let user_header_val = header::HeaderValue::from_str(&self.user)?;
-        let token_header_name =
-            header::HeaderName::from_bytes(SLURM_TOKEN.to_lowercase().as_bytes())?;
-        let token_header_val = header::HeaderValue::from_str(&self.token)?;
-
-        // Set default headers
-        let mut headers = header::HeaderMap::new();
-        headers.append(user_header_name, user_header_val);
-        headers.append(token_header_name, token_header_val);
-        headers.append(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-
-        // Start building up our request
-        let mut request_builder = self.client.request(method.clone(), url).headers(headers);
-
-        // if we have query variable, add it to our Url
-        match query {
-            None => (),
-            Some(q) => {
-                request_builder = request_builder.query(&q);
-            }
-        }
-
-        // Add the body if our request method is something other than
-        // GET or DELETE
-        if method != Method::GET && method != Method::DELETE {
-            request_builder = request_builder.json(&body);
-        }
-
-        // Build it!
-        Ok(request_builder.build()?)
+        self.transport.request("slurm", method, path, body, query)
     }
 
     /// Ping test!
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038Ping>
     pub async fn ping(&self) -> Result<Pings> {
         let request = self.request(Method::GET, "ping", (), None)?;
-
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: Pings = response.json().await?;
-        Ok(r)
+        self.execute(request).await
     }
 
     /// Get all parition information
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetPartitions>
     pub async fn get_partitions(&self) -> Result<PartitionsResponse> {
         let request = self.request(Method::GET, "partitions", (), None)?;
+        self.execute(request).await
+    }
 
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: PartitionsResponse = response.json().await?;
-        Ok(r)
+    /// Get all partition information, but only if any of it changed since
+    /// `update_time`, in seconds since the epoch.
+    pub async fn get_partitions_since(
+        &self,
+        update_time: i64,
+    ) -> Result<Conditional<PartitionsResponse>> {
+        self.get_since::<PartitionsResponse>("partitions", update_time, |r| r.last_update)
+            .await
     }
 
     /// Get a specific parition's information
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetPartition>
     pub async fn get_partition(&self, partition: &str) -> Result<PartitionsResponse> {
         let request = self.request(Method::GET, &format!("partition/{partition}"), (), None)?;
-
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: PartitionsResponse = response.json().await?;
-        Ok(r)
+        self.execute(request).await
     }
 
     /// Get all nodes information
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetNodes>
     pub async fn get_nodes(&self) -> Result<NodesResponse> {
         let request = self.request(Method::GET, "nodes", (), None)?;
+        self.execute(request).await
+    }
 
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: NodesResponse = response.json().await?;
-        Ok(r)
+    /// Get all nodes information, but only if any of it changed since
+    /// `update_time`, in seconds since the epoch.
+    pub async fn get_nodes_since(&self, update_time: i64) -> Result<Conditional<NodesResponse>> {
+        self.get_since::<NodesResponse>("nodes", update_time, |r| r.last_update)
+            .await
     }
 
     /// Get a specific node's information
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetNode>
     pub async fn get_node(&self, node: &str) -> Result<NodesResponse> {
         let request = self.request(Method::GET, &format!("node/{node}"), (), None)?;
+        self.execute(request).await
+    }
 
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
+    /// Update a node's state, reason, features or weight. Setting the state
+    /// to DRAIN, DOWN or FAIL without a reason is rejected before anything
+    /// is sent. Errors reported by Slurm are handled by the `ErrorPolicy`.
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038UpdateNode>
+    pub async fn update_node(&self, node: &str, update: &NodeUpdate) -> Result<()> {
+        if let Some(state) = update.state.iter().find(|s| s.requires_reason()) {
+            if update.reason.as_deref().is_none_or(str::is_empty) {
+                return Err(SlurmError::InvalidInput(format!(
+                    "setting node state {state:?} requires a reason"
+                )));
             }
-        };
+        }
+
+        let request = self.request(Method::POST, &format!("node/{node}"), update, None)?;
+        self.execute::<Status>(request).await?;
+        Ok(())
+    }
 
-        let r: NodesResponse = response.json().await?;
-        Ok(r)
+    /// Delete a dynamic node. Errors reported by Slurm are handled by the
+    /// `ErrorPolicy`.
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038DeleteNode>
+    pub async fn delete_node(&self, node: &str) -> Result<()> {
+        let request = self.request(Method::DELETE, &format!("node/{node}"), (), None)?;
+        self.execute::<Status>(request).await?;
+        Ok(())
     }
 
     /// Get diagnostics information
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038Diag>
     pub async fn get_diag(&self) -> Result<Diag> {
         let request = self.request(Method::GET, "diag", (), None)?;
-
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: Diag = response.json().await?;
-        Ok(r)
+        self.execute(request).await
     }
 
     /// Get all reservations
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetReservations>
     pub async fn get_reservations(&self) -> Result<ReservationsResponse> {
         let request = self.request(Method::GET, "reservations", (), None)?;
-
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: ReservationsResponse = response.json().await?;
-        Ok(r)
+        self.execute(request).await
     }
 
     /// Get a specific reservation
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetReservation>
     pub async fn get_reservation(&self, reservation: &str) -> Result<ReservationsResponse> {
         let request = self.request(Method::GET, &format!("reservation/{reservation}"), (), None)?;
-
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: ReservationsResponse = response.json().await?;
-        Ok(r)
+        self.execute(request).await
     }
 
     /// Get all jobs
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetJobs>
     pub async fn get_jobs(&self) -> Result<JobsResponse> {
         let request = self.request(Method::GET, "jobs", (), None)?;
+        self.execute(request).await
+    }
 
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: JobsResponse = response.json().await?;
-        Ok(r)
+    /// Get all jobs, but only if any of them changed since `update_time`,
+    /// in seconds since the epoch.
+    pub async fn get_jobs_since(&self, update_time: i64) -> Result<Conditional<JobsResponse>> {
+        self.get_since::<JobsResponse>("jobs", update_time, |r| r.last_update)
+            .await
     }
 
     /// Get a specific job
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038GetJob>
     pub async fn get_job(&self, job: &str) -> Result<JobsResponse> {
         let request = self.request(Method::GET, &format!("job/{job}"), (), None)?;
+        self.execute(request).await
+    }
 
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
+    /// Cancel a job, or send it a signal. Without a signal slurmctld
+    /// sends `SIGKILL`. Errors reported by Slurm are handled by the
+    /// `ErrorPolicy`.
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038CancelJob>
+    pub async fn cancel_job(&self, job: &str, signal: Option<&str>) -> Result<()> {
+        let query = signal.map(|s| vec![("signal", s.to_string())]);
+        let request = self.request(Method::DELETE, &format!("job/{job}"), (), query)?;
+        self.execute::<Status>(request).await?;
+        Ok(())
+    }
 
-        let r: JobsResponse = response.json().await?;
-        Ok(r)
+    /// Update a pending or running job. Errors reported by Slurm are
+    /// handled by the `ErrorPolicy`.
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038UpdateJob>
+    pub async fn update_job(&self, job: &str, update: &JobUpdate) -> Result<()> {
+        let request = self.request(Method::POST, &format!("job/{job}"), update, None)?;
+        self.execute::<Status>(request).await?;
+        Ok(())
     }
 
     /// Get licenses
     /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038SlurmctldGetLicenses>
     pub async fn get_licenses(&self) -> Result<Licenses> {
         let request = self.request(Method::GET, "licenses", (), None)?;
+        self.execute(request).await
+    }
 
-        let response = self.client.execute(request).await?;
-        match response.status() {
-            StatusCode::OK => (),
-            status => {
-                bail!("status code: {}, body: {}", status, response.text().await?);
-            }
-        };
-
-        let r: Licenses = response.json().await?;
-        Ok(r)
+    /// Submit a new job
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmV0038SubmitJob>
+    pub async fn submit_job(&self, job: &JobSubmission) -> Result<JobSubmitResponse> {
+        let request = self.request(Method::POST, "job/submit", job, None)?;
+        self.execute(request).await
     }
 }
 
 /// Entrypoint for interacting with the API.
 /// To authenticate with the API, we need a user and a token.
+#[derive(Clone)]
 pub struct SlurmDB {
-    user: String,
-    token: String,
-    endpoint: Url,
-    client: Arc<Client>,
+    transport: Arc<Transport>,
+    error_policy: ErrorPolicy,
 }
 
 impl SlurmDB {
-    /// Create a new Slurm client struct. It takes any type that can convert
+    /// Start building a new SlurmDB client. Unlike `new` and `new_from_env`,
+    /// the builder reports problems as errors instead of panicking.
+    pub fn builder() -> SlurmBuilder<SlurmDB> {
+        SlurmBuilder::new()
+    }
+
+    /// Create a new SlurmDB client struct. It takes any type that can convert
     /// into a &str and any type that can convert into a URL for the endpoint.
     /// Since this lib is useless withouth a client to connect with, this
-    /// will panic if creating a client fails.
+    /// will panic if creating a client fails. Use `SlurmDB::builder()` to
+    /// handle that error instead.
     pub fn new<U, T, L>(user: U, token: T, url: L) -> Self
     where
         U: ToString,
         T: ToString,
         L: ToString,
     {
-        let client = Client::builder().build();
-        match client {
-            Ok(c) => SlurmDB {
-                user: user.to_string(),
-                token: token.to_string(),
-                endpoint: Url::parse(&url.to_string()).expect("Unable to parse endpoint into URL!"),
-                client: Arc::new(c),
-            },
-            Err(e) => panic!("Unable to create client: {e:?}"),
-        }
+        SlurmDB::builder()
+            .user(user)
+            .token(token)
+            .endpoint(url)
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
     }
 
     /// Create a new SlurmDB client struct from environment variables.
-    /// It takes any type that can convert into a &str.
     /// Since this lib is useless withouth a client to connect with, this
-    /// will panic if creating a client fails.
+    /// will panic if a variable is missing or creating a client fails.
+    /// Use `SlurmDB::builder().load_env()` to handle that error instead.
     pub fn new_from_env() -> Self {
-        let endpoint =
-            env::var("X_SLURM_ENDPOINT").expect("env variable 'X_SLURM_ENDPOINT' should be set");
-        let user =
-            env::var("X_SLURM_USER_NAME").expect("env variable 'X_SLURM_USER_NAME' should be set");
-        let token = env::var("X_SLURM_USER_TOKEN")
-            .expect("env variable 'X_SLURM_USER_TOKEN' should be set");
+        SlurmDB::builder()
+            .load_env()
+            .build()
+            .unwrap_or_else(|e| panic!("Unable to create client: {e}"))
+    }
 
-        SlurmDB::new(user, token, endpoint)
+    /// Create a client for a local slurmrestd listening on a unix socket
+    /// with `auth/local`, e.g. `SlurmDB::unix("/run/slurmrestd.sock")`. No
+    /// user name or token is sent; slurmrestd authenticates the process
+    /// connecting to the socket.
+    #[cfg(unix)]
+    pub fn unix<P: Into<std::path::PathBuf>>(path: P) -> Result<Self> {
+        SlurmDB::builder().unix_socket(path).local_auth().build()
+    }
+
+    /// Set how errors Slurm reports in an otherwise successful response
+    /// are handled. Defaults to `ErrorPolicy::Strict`.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// The shared client this handle was derived from.
+    pub fn client(&self) -> SlurmClient {
+        SlurmClient {
+            transport: self.transport.clone(),
+            error_policy: self.error_policy,
+        }
+    }
+
+    /// Version of the REST API requests are sent to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.transport.version
+    }
+
+    // Send a request and parse the response, applying our error policy.
+    async fn execute<T>(&self, request: Request) -> Result<T>
+    where
+        T: DeserializeOwned + SlurmResponse,
+    {
+        let r: T = self.transport.execute("slurmdb", request).await?;
+        self.error_policy.check(r)
     }
 
     // This will be our internal request builder.
@@ -358,712 +459,1901 @@ impl SlurmDB {
     where
         B: Serialize,
     {
-        // https://slurm-endpoint/{slurm,slurmdb}/v0.0.38/{nodes, diag, etc..}
-        let url_path = format!("slurm/{}/{}", SLURM_API_VERSION, path);
-        let url = self.endpoint.join(&url_path)?;
-
-        // Build auth headers
-        let user_header_name =
-            header::HeaderName::from_bytes(SLURM_USER.to_lowercase().as_bytes())?;
-        let user_header_val = header::HeaderValue::from_str(&self.user)?;
-        let token_header_name =
-            header::HeaderName::from_bytes(SLURM_TOKEN.to_lowercase().as_bytes())?;
-        let token_header_val = header::HeaderValue::from_str(&self.token)?;
-
-        // Set default headers
-        let mut headers = header::HeaderMap::new();
-        headers.append(user_header_name, user_header_val);
-        headers.append(token_header_name, token_header_val);
-        headers.append(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
+        self.transport.request("slurmdb", method, path, body, query)
+    }
+
+    /// Get all accounts
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetAccounts>
+    pub async fn get_accounts(&self) -> Result<AccountsResponse> {
+        let request = self.request(Method::GET, "accounts", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get a specific account
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetAccount>
+    pub async fn get_account(&self, account: &str) -> Result<AccountsResponse> {
+        let request = self.request(Method::GET, &format!("account/{account}"), (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get all associations
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetAssociations>
+    pub async fn get_associations(&self) -> Result<AssociationsResponse> {
+        let request = self.request(Method::GET, "associations", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get all clusters
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetClusters>
+    pub async fn get_clusters(&self) -> Result<ClustersResponse> {
+        let request = self.request(Method::GET, "clusters", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get all QOS
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetQos>
+    pub async fn get_qos(&self) -> Result<QosResponse> {
+        let request = self.request(Method::GET, "qos", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get all TRES
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetTres>
+    pub async fn get_tres(&self) -> Result<TresResponse> {
+        let request = self.request(Method::GET, "tres", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get all users
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetUsers>
+    pub async fn get_users(&self) -> Result<UsersResponse> {
+        let request = self.request(Method::GET, "users", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get a specific user
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetUser>
+    pub async fn get_user(&self, user: &str) -> Result<UsersResponse> {
+        let request = self.request(Method::GET, &format!("user/{user}"), (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get all wckeys
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetWckeys>
+    pub async fn get_wckeys(&self) -> Result<WckeysResponse> {
+        let request = self.request(Method::GET, "wckeys", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get the full slurmdbd configuration
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetDbConfig>
+    pub async fn get_config(&self) -> Result<DbConfig> {
+        let request = self.request(Method::GET, "config", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get slurmdbd diagnostics information
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038Diag>
+    pub async fn get_diag(&self) -> Result<DbDiag> {
+        let request = self.request(Method::GET, "diag", (), None)?;
+        self.execute(request).await
+    }
+
+    /// Get accounting information for jobs matching the query. Use
+    /// `DbJobsQuery::default()` to get every job slurmdbd is willing to return.
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetJobs>
+    pub async fn get_jobs(&self, query: &DbJobsQuery) -> Result<DbJobsResponse> {
+        let request = self.request(Method::GET, "jobs", (), Some(query.to_query()))?;
+        self.execute(request).await
+    }
+
+    /// Get accounting information for a specific job
+    /// SEE: <https://slurm.schedmd.com/rest_api.html#slurmdbV0038GetJob>
+    pub async fn get_job(&self, job: &str) -> Result<DbJobsResponse> {
+        let request = self.request(Method::GET, &format!("job/{job}"), (), None)?;
+        self.execute(request).await
+    }
+}
+
+/// Filters for `SlurmDB::get_jobs`, mirroring the options of `sacct`.
+/// Every filter is optional; list filters are sent comma separated.
+/// Times may be anything slurmdbd accepts, such as a unix timestamp or
+/// `2023-04-01T00:00:00`.
+#[derive(Debug, Default, Clone)]
+pub struct DbJobsQuery {
+    start_time: Option<String>,
+    end_time: Option<String>,
+    submit_time: Option<String>,
+    users: Vec<String>,
+    accounts: Vec<String>,
+    partitions: Vec<String>,
+    states: Vec<String>,
+    job_names: Vec<String>,
+    qos: Vec<String>,
+    clusters: Vec<String>,
+    groups: Vec<String>,
+    wckeys: Vec<String>,
+    reservations: Vec<String>,
+    nodes: Option<String>,
+    skip_steps: Option<bool>,
+}
+
+impl DbJobsQuery {
+    /// Create an empty query that matches every job.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return jobs that were running after this time.
+    pub fn start_time<T: ToString>(mut self, time: T) -> Self {
+        self.start_time = Some(time.to_string());
+        self
+    }
 
-        // Start building up our request
-        let mut request_builder = self.client.request(method.clone(), url).headers(headers);
+    /// Only return jobs that were running before this time.
+    pub fn end_time<T: ToString>(mut self, time: T) -> Self {
+        self.end_time = Some(time.to_string());
+        self
+    }
+
+    /// Only return jobs submitted after this time.
+    pub fn submit_time<T: ToString>(mut self, time: T) -> Self {
+        self.submit_time = Some(time.to_string());
+        self
+    }
+
+    /// Only return jobs owned by these users.
+    pub fn users<I, S>(mut self, users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.users = users.into_iter().map(|u| u.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs charged to these accounts.
+    pub fn accounts<I, S>(mut self, accounts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.accounts = accounts.into_iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs that ran in these partitions.
+    pub fn partitions<I, S>(mut self, partitions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.partitions = partitions.into_iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs in these states, e.g. `COMPLETED` or `FAILED`.
+    pub fn states<I, S>(mut self, states: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.states = states.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs with these names.
+    pub fn job_names<I, S>(mut self, job_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.job_names = job_names.into_iter().map(|n| n.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs that ran under these QOS.
+    pub fn qos<I, S>(mut self, qos: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.qos = qos.into_iter().map(|q| q.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs that ran on these clusters.
+    pub fn clusters<I, S>(mut self, clusters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.clusters = clusters.into_iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs owned by these groups.
+    pub fn groups<I, S>(mut self, groups: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.groups = groups.into_iter().map(|g| g.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs with these wckeys.
+    pub fn wckeys<I, S>(mut self, wckeys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.wckeys = wckeys.into_iter().map(|w| w.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs that ran in these reservations.
+    pub fn reservations<I, S>(mut self, reservations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.reservations = reservations.into_iter().map(|r| r.to_string()).collect();
+        self
+    }
+
+    /// Only return jobs that ran on any of the nodes in this hostlist.
+    pub fn nodes<T: ToString>(mut self, nodes: T) -> Self {
+        self.nodes = Some(nodes.to_string());
+        self
+    }
+
+    /// Leave job steps out of the response.
+    pub fn skip_steps(mut self, skip: bool) -> Self {
+        self.skip_steps = Some(skip);
+        self
+    }
+
+    // Turn the filters into the query pairs slurmdbd expects.
+    fn to_query(&self) -> Vec<(&str, String)> {
+        let mut query = Vec::new();
+
+        let single = [
+            ("start_time", &self.start_time),
+            ("end_time", &self.end_time),
+            ("submit_time", &self.submit_time),
+            ("node", &self.nodes),
+        ];
+        for (key, value) in single {
+            if let Some(v) = value {
+                query.push((key, v.clone()));
+            }
+        }
 
-        // if we have query variable, add it to our Url
-        match query {
-            None => (),
-            Some(q) => {
-                request_builder = request_builder.query(&q);
+        let lists = [
+            ("users", &self.users),
+            ("account", &self.accounts),
+            ("partition", &self.partitions),
+            ("state", &self.states),
+            ("job_name", &self.job_names),
+            ("qos", &self.qos),
+            ("cluster", &self.clusters),
+            ("groups", &self.groups),
+            ("wckey", &self.wckeys),
+            ("reservation", &self.reservations),
+        ];
+        for (key, values) in lists {
+            if !values.is_empty() {
+                query.push((key, values.join(",")));
             }
         }
 
-        // Add the body if our request method is something other than
-        // GET or DELETE
-        if method != Method::GET && method != Method::DELETE {
-            request_builder = request_builder.json(&body);
+        if let Some(skip) = self.skip_steps {
+            query.push(("skip_steps", skip.to_string()));
         }
 
-        // Build it!
-        Ok(request_builder.build()?)
+        query
     }
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Licenses {
-    #[serde(default)]
-    pub errors: Vec<Error>,
-    #[serde(default)]
-    pub licenses: Vec<License>,
+/// A job to submit with `Slurm::submit_job`.
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_job_submission>
+#[derive(Debug, Clone, JsonSchema, Serialize)]
+pub struct JobSubmission {
+    script: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    job: Option<JobProperties>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    jobs: Vec<JobProperties>,
 }
 
-#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct License {
-    #[serde(default, rename = "LicenseName")]
-    pub license_name: Option<String>,
-    #[serde(default, rename = "Total")]
-    pub total: Option<i64>,
-    #[serde(default, rename = "Used")]
-    pub used: Option<i64>,
-    #[serde(default, rename = "Free")]
-    pub free: Option<i64>,
-    #[serde(default, rename = "Reserved")]
-    pub reserved: Option<i64>,
-    #[serde(default, rename = "Remote")]
-    pub remote: Option<bool>,
+impl JobSubmission {
+    /// Submit a single batch job running `script`.
+    pub fn new<S: ToString>(script: S, job: JobProperties) -> Self {
+        JobSubmission {
+            script: script.to_string(),
+            job: Some(job),
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Submit a heterogeneous job. Each entry in `components` describes one
+    /// component of the job and all of them run the same `script`.
+    pub fn heterogeneous<S, I>(script: S, components: I) -> Self
+    where
+        S: ToString,
+        I: IntoIterator<Item = JobProperties>,
+    {
+        JobSubmission {
+            script: script.to_string(),
+            job: None,
+            jobs: components.into_iter().collect(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct JobsResponse {
-    #[serde(default)]
-    pub meta: Meta,
-    #[serde(default)]
-    pub errors: Vec<Error>,
-    #[serde(default)]
-    pub jobs: Vec<JobResponseProperties>,
+/// Properties of a job to submit. Anything left unset falls back to the
+/// defaults slurmctld would apply to an `sbatch` without that option.
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_job_properties>
+#[derive(Debug, Default, Clone, JsonSchema, Serialize)]
+pub struct JobProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    argv: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    array: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    begin_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constraints: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cpus_per_gpu: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cpus_per_task: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_working_directory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deadline: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dependency: Option<String>,
+    environment: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gres: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gpus: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gpus_per_node: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gpus_per_task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    licenses: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mail_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mail_user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory_per_cpu: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory_per_gpu: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory_per_node: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nice: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nodes: Option<[i64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qos: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requeue: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reservation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    standard_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    standard_input: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    standard_output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tasks: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tasks_per_node: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_minimum: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tres_per_job: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tres_per_node: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tres_per_task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wckey: Option<String>,
 }
 
-#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct JobResponseProperties {
-    #[serde(default)]
-    pub account: Option<String>,
-    #[serde(default)]
-    pub accrue_time: Option<i64>,
-    #[serde(default)]
-    pub admin_comment: Option<String>,
-    #[serde(default)]
-    pub array_job_id: Option<i64>,
-    #[serde(default)]
-    pub array_task_id: Option<i64>,
-    #[serde(default)]
-    pub array_max_tasks: Option<i64>,
-    #[serde(default)]
-    pub array_task_string: Option<String>,
-    #[serde(default)]
-    pub association_id: Option<i64>,
-    #[serde(default)]
-    pub batch_features: Option<String>,
-    #[serde(default)]
-    pub batch_flag: Option<bool>,
-    #[serde(default)]
-    pub batch_host: Option<String>,
-    #[serde(default)]
-    pub flags: Option<Vec<String>>,
-    #[serde(default)]
-    pub burst_buffer: Option<String>,
-    #[serde(default)]
-    pub burst_buffer_state: Option<String>,
-    #[serde(default)]
-    pub cluster: Option<String>,
-    #[serde(default)]
-    pub cluster_features: Option<String>,
-    #[serde(default)]
-    pub command: Option<String>,
-    #[serde(default)]
-    pub comment: Option<String>,
-    #[serde(default)]
-    pub container: Option<String>,
-    #[serde(default)]
-    pub contiguous: Option<bool>,
-    #[serde(default)]
-    pub core_spec: Option<String>,
-    #[serde(default)]
-    pub thread_spec: Option<String>,
-    #[serde(default)]
-    pub cores_per_socket: Option<String>,
-    #[serde(default)]
-    pub billable_tres: Option<f64>,
-    #[serde(default)]
-    pub cpus_per_task: Option<String>,
-    #[serde(default)]
-    pub cpu_frequency_minimum: Option<String>,
-    #[serde(default)]
-    pub cpu_frequency_maximum: Option<String>,
-    #[serde(default)]
-    pub cpu_frequency_governor: Option<String>,
-    #[serde(default)]
-    pub cpus_per_tres: Option<String>,
-    #[serde(default)]
-    pub deadline: Option<i64>,
-    #[serde(default)]
-    pub delay_boot: Option<i64>,
-    #[serde(default)]
-    pub dependency: Option<String>,
-    #[serde(default)]
-    pub derived_exit_code: Option<i64>,
-    #[serde(default)]
-    pub eligible_time: Option<i64>,
-    #[serde(default)]
-    pub end_time: Option<i64>,
-    #[serde(default)]
-    pub excluded_nodes: Option<String>,
-    #[serde(default)]
-    pub exit_code: Option<i64>,
-    #[serde(default)]
-    pub features: Option<String>,
-    #[serde(default)]
-    pub federation_origin: Option<String>,
-    #[serde(default)]
-    pub federation_siblings_active: Option<String>,
-    #[serde(default)]
-    pub federation_siblings_viable: Option<String>,
-    #[serde(default)]
-    pub gres_detail: Option<Vec<String>>,
-    #[serde(default)]
-    pub group_id: Option<i64>,
-    #[serde(default)]
-    pub job_id: Option<i64>,
-    #[serde(default)]
-    pub job_resources: Option<JobResources>,
-    #[serde(default)]
-    pub job_state: Option<String>,
-    #[serde(default)]
-    pub last_sched_evaluation: Option<i64>,
-    #[serde(default)]
-    pub licenses: Option<String>,
-    #[serde(default)]
-    pub max_cpus: Option<i64>,
-    #[serde(default)]
-    pub max_nodes: Option<i64>,
-    #[serde(default)]
-    pub mcs_label: Option<String>,
-    #[serde(default)]
-    pub memory_per_tres: Option<String>,
-    #[serde(default)]
-    pub name: Option<String>,
+impl JobProperties {
+    /// Create an empty set of job properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge the job to this account.
+    pub fn account<S: ToString>(mut self, account: S) -> Self {
+        self.account = Some(account.to_string());
+        self
+    }
+
+    /// Arguments passed to the batch script.
+    pub fn argv<I, S>(mut self, argv: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.argv = argv.into_iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    /// Submit a job array, e.g. `0-15%4`.
+    pub fn array<S: ToString>(mut self, array: S) -> Self {
+        self.array = Some(array.to_string());
+        self
+    }
+
+    /// Do not start the job before this unix timestamp.
+    pub fn begin_time(mut self, begin_time: i64) -> Self {
+        self.begin_time = Some(begin_time);
+        self
+    }
+
+    /// Arbitrary comment attached to the job.
+    pub fn comment<S: ToString>(mut self, comment: S) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Node features the job requires, e.g. `intel&ib`.
+    pub fn constraints<S: ToString>(mut self, constraints: S) -> Self {
+        self.constraints = Some(constraints.to_string());
+        self
+    }
+
+    /// Number of CPUs required per allocated GPU.
+    pub fn cpus_per_gpu(mut self, cpus: i64) -> Self {
+        self.cpus_per_gpu = Some(cpus);
+        self
+    }
+
+    /// Number of CPUs required per task.
+    pub fn cpus_per_task(mut self, cpus: i64) -> Self {
+        self.cpus_per_task = Some(cpus);
+        self
+    }
+
+    /// Directory the batch script is run from.
+    pub fn current_working_directory<S: ToString>(mut self, directory: S) -> Self {
+        self.current_working_directory = Some(directory.to_string());
+        self
+    }
+
+    /// Remove the job if it cannot finish before this unix timestamp.
+    pub fn deadline(mut self, deadline: i64) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Dependencies on other jobs, e.g. `afterok:1234`.
+    pub fn dependency<S: ToString>(mut self, dependency: S) -> Self {
+        self.dependency = Some(dependency.to_string());
+        self
+    }
+
+    /// Set a single environment variable for the job.
+    pub fn env<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.environment.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Set several environment variables for the job.
+    pub fn environment<I, K, V>(mut self, environment: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: ToString,
+    {
+        self.environment.extend(
+            environment
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+        self
+    }
+
+    /// Generic resources required per node, e.g. `gpu:a100:2`.
+    pub fn gres<S: ToString>(mut self, gres: S) -> Self {
+        self.gres = Some(gres.to_string());
+        self
+    }
+
+    /// GPUs required for the whole job, e.g. `a100:4`.
+    pub fn gpus<S: ToString>(mut self, gpus: S) -> Self {
+        self.gpus = Some(gpus.to_string());
+        self
+    }
+
+    /// GPUs required on each node.
+    pub fn gpus_per_node<S: ToString>(mut self, gpus: S) -> Self {
+        self.gpus_per_node = Some(gpus.to_string());
+        self
+    }
+
+    /// GPUs required for each task.
+    pub fn gpus_per_task<S: ToString>(mut self, gpus: S) -> Self {
+        self.gpus_per_task = Some(gpus.to_string());
+        self
+    }
+
+    /// Submit the job in a held state.
+    pub fn hold(mut self, hold: bool) -> Self {
+        self.hold = Some(hold);
+        self
+    }
+
+    /// Licenses required by the job, e.g. `matlab:2`.
+    pub fn licenses<S: ToString>(mut self, licenses: S) -> Self {
+        self.licenses = Some(licenses.to_string());
+        self
+    }
+
+    /// Events to send mail for, e.g. `END,FAIL`.
+    pub fn mail_type<S: ToString>(mut self, mail_type: S) -> Self {
+        self.mail_type = Some(mail_type.to_string());
+        self
+    }
+
+    /// User to send mail to.
+    pub fn mail_user<S: ToString>(mut self, mail_user: S) -> Self {
+        self.mail_user = Some(mail_user.to_string());
+        self
+    }
+
+    /// Memory required per allocated CPU, in megabytes.
+    pub fn memory_per_cpu(mut self, megabytes: i64) -> Self {
+        self.memory_per_cpu = Some(megabytes);
+        self
+    }
+
+    /// Memory required per allocated GPU, in megabytes.
+    pub fn memory_per_gpu(mut self, megabytes: i64) -> Self {
+        self.memory_per_gpu = Some(megabytes);
+        self
+    }
+
+    /// Memory required per node, in megabytes.
+    pub fn memory_per_node(mut self, megabytes: i64) -> Self {
+        self.memory_per_node = Some(megabytes);
+        self
+    }
+
+    /// Name of the job.
+    pub fn name<S: ToString>(mut self, name: S) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Scheduling priority adjustment.
+    pub fn nice(mut self, nice: i64) -> Self {
+        self.nice = Some(nice);
+        self
+    }
+
+    /// Minimum and maximum number of nodes to allocate.
+    pub fn nodes(mut self, min: i64, max: i64) -> Self {
+        self.nodes = Some([min, max]);
+        self
+    }
+
+    /// Partition to run the job in.
+    pub fn partition<S: ToString>(mut self, partition: S) -> Self {
+        self.partition = Some(partition.to_string());
+        self
+    }
+
+    /// Explicit job priority. Only honored for operators and administrators.
+    pub fn priority(mut self, priority: i64) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Quality of service to run the job under.
+    pub fn qos<S: ToString>(mut self, qos: S) -> Self {
+        self.qos = Some(qos.to_string());
+        self
+    }
+
+    /// Whether the job may be requeued.
+    pub fn requeue(mut self, requeue: bool) -> Self {
+        self.requeue = Some(requeue);
+        self
+    }
+
+    /// Run the job in this reservation.
+    pub fn reservation<S: ToString>(mut self, reservation: S) -> Self {
+        self.reservation = Some(reservation.to_string());
+        self
+    }
+
+    /// Path to write the job's stderr to.
+    pub fn standard_error<S: ToString>(mut self, path: S) -> Self {
+        self.standard_error = Some(path.to_string());
+        self
+    }
+
+    /// Path to read the job's stdin from.
+    pub fn standard_input<S: ToString>(mut self, path: S) -> Self {
+        self.standard_input = Some(path.to_string());
+        self
+    }
+
+    /// Path to write the job's stdout to.
+    pub fn standard_output<S: ToString>(mut self, path: S) -> Self {
+        self.standard_output = Some(path.to_string());
+        self
+    }
+
+    /// Number of tasks to run.
+    pub fn tasks(mut self, tasks: i64) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
+    /// Number of tasks to run on each node.
+    pub fn tasks_per_node(mut self, tasks: i64) -> Self {
+        self.tasks_per_node = Some(tasks);
+        self
+    }
+
+    /// Wall clock limit, in minutes.
+    pub fn time_limit(mut self, minutes: i64) -> Self {
+        self.time_limit = Some(minutes);
+        self
+    }
+
+    /// Minimum wall clock limit the job accepts, in minutes.
+    pub fn time_minimum(mut self, minutes: i64) -> Self {
+        self.time_minimum = Some(minutes);
+        self
+    }
+
+    /// Trackable resources required for the whole job, e.g.
+    /// `gres/gpu:a100=4,license/matlab=1`.
+    pub fn tres_per_job<S: ToString>(mut self, tres: S) -> Self {
+        self.tres_per_job = Some(tres.to_string());
+        self
+    }
+
+    /// Trackable resources required on each node, e.g. `gres/gpu=2`.
+    pub fn tres_per_node<S: ToString>(mut self, tres: S) -> Self {
+        self.tres_per_node = Some(tres.to_string());
+        self
+    }
+
+    /// Trackable resources required for each task, e.g. `cpu=2,gres/gpu=1`.
+    pub fn tres_per_task<S: ToString>(mut self, tres: S) -> Self {
+        self.tres_per_task = Some(tres.to_string());
+        self
+    }
+
+    /// Workload characterization key.
+    pub fn wckey<S: ToString>(mut self, wckey: S) -> Self {
+        self.wckey = Some(wckey.to_string());
+        self
+    }
+}
+
+/// Changes to apply to a job with `Slurm::update_job`. Only the fields
+/// that are set are sent to slurmctld.
+#[derive(Debug, Default, Clone, JsonSchema, Serialize)]
+pub struct JobUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    begin_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deadline: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dependency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nice: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qos: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requeue: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reservation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_minimum: Option<i64>,
+}
+
+impl JobUpdate {
+    /// Create an update that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge the job to a different account.
+    pub fn account<S: ToString>(mut self, account: S) -> Self {
+        self.account = Some(account.to_string());
+        self
+    }
+
+    /// Do not start the job before this unix timestamp.
+    pub fn begin_time(mut self, begin_time: i64) -> Self {
+        self.begin_time = Some(begin_time);
+        self
+    }
+
+    /// Replace the job's comment.
+    pub fn comment<S: ToString>(mut self, comment: S) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Remove the job if it cannot finish before this unix timestamp.
+    pub fn deadline(mut self, deadline: i64) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Replace the job's dependencies, e.g. `afterok:1234`.
+    pub fn dependency<S: ToString>(mut self, dependency: S) -> Self {
+        self.dependency = Some(dependency.to_string());
+        self
+    }
+
+    /// Hold a pending job so it will not be scheduled.
+    pub fn hold(mut self) -> Self {
+        self.hold = Some(true);
+        self
+    }
+
+    /// Release a held job.
+    pub fn release(mut self) -> Self {
+        self.hold = Some(false);
+        self
+    }
+
+    /// Rename the job.
+    pub fn name<S: ToString>(mut self, name: S) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Change the job's scheduling priority adjustment.
+    pub fn nice(mut self, nice: i64) -> Self {
+        self.nice = Some(nice);
+        self
+    }
+
+    /// Move a pending job to another partition.
+    pub fn partition<S: ToString>(mut self, partition: S) -> Self {
+        self.partition = Some(partition.to_string());
+        self
+    }
+
+    /// Set an explicit priority. Only honored for operators and administrators.
+    pub fn priority(mut self, priority: i64) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Change the quality of service the job runs under.
+    pub fn qos<S: ToString>(mut self, qos: S) -> Self {
+        self.qos = Some(qos.to_string());
+        self
+    }
+
+    /// Whether the job may be requeued.
+    pub fn requeue(mut self, requeue: bool) -> Self {
+        self.requeue = Some(requeue);
+        self
+    }
+
+    /// Move the job into a reservation.
+    pub fn reservation<S: ToString>(mut self, reservation: S) -> Self {
+        self.reservation = Some(reservation.to_string());
+        self
+    }
+
+    /// Change the wall clock limit, in minutes.
+    pub fn time_limit(mut self, minutes: i64) -> Self {
+        self.time_limit = Some(minutes);
+        self
+    }
+
+    /// Change the minimum wall clock limit, in minutes.
+    pub fn time_minimum(mut self, minutes: i64) -> Self {
+        self.time_minimum = Some(minutes);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct JobSubmitResponse {
     #[serde(default)]
-    pub nodes: Option<String>,
+    pub meta: Meta,
     #[serde(default)]
-    pub nice: Option<i64>,
+    pub errors: Vec<Error>,
     #[serde(default)]
-    pub tasks_per_core: Option<i64>,
+    pub job_id: Option<i64>,
     #[serde(default)]
-    pub tasks_per_socket: Option<i64>,
+    pub step_id: Option<String>,
     #[serde(default)]
-    pub tasks_per_board: Option<i64>,
+    pub job_submit_user_msg: Option<String>,
+}
+
+/// States a node can be put into with `Slurm::update_node`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NodeState {
+    Down,
+    Drain,
+    Fail,
+    Future,
+    Idle,
+    NoResp,
+    PowerDown,
+    PowerDownAsap,
+    PowerDownForce,
+    PowerUp,
+    Resume,
+    Undrain,
+}
+
+impl NodeState {
+    /// Whether slurmctld requires a reason when moving a node to this state.
+    pub fn requires_reason(&self) -> bool {
+        matches!(self, NodeState::Down | NodeState::Drain | NodeState::Fail)
+    }
+}
+
+/// Changes to apply to a node with `Slurm::update_node`. Only the fields
+/// that are set are sent to slurmctld.
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_update_node_msg>
+#[derive(Debug, Default, Clone, JsonSchema, Serialize)]
+pub struct NodeUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    features: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    features_act: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gres: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason_uid: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    state: Vec<NodeState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<i64>,
+}
+
+impl NodeUpdate {
+    /// Create an update that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drain the node for `reason`.
+    pub fn drain<S: ToString>(reason: S) -> Self {
+        Self::new().state(NodeState::Drain).reason(reason)
+    }
+
+    /// Return a drained or down node to service.
+    pub fn resume() -> Self {
+        Self::new().state(NodeState::Resume)
+    }
+
+    /// Replace the node's comment.
+    pub fn comment<S: ToString>(mut self, comment: S) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    /// Replace the node's extra data.
+    pub fn extra<S: ToString>(mut self, extra: S) -> Self {
+        self.extra = Some(extra.to_string());
+        self
+    }
+
+    /// Replace the node's available features.
+    pub fn features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.features = Some(features.into_iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    /// Replace the node's active features.
+    pub fn active_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.features_act = Some(features.into_iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    /// Replace the node's generic resources, e.g. `gpu:a100:4`.
+    pub fn gres<S: ToString>(mut self, gres: S) -> Self {
+        self.gres = Some(gres.to_string());
+        self
+    }
+
+    /// Reason the node's state is being changed.
+    pub fn reason<S: ToString>(mut self, reason: S) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+
+    /// User the reason is recorded against. Needed when updating as root.
+    pub fn reason_uid<S: ToString>(mut self, user: S) -> Self {
+        self.reason_uid = Some(user.to_string());
+        self
+    }
+
+    /// Add a state to apply to the node. States can be combined, e.g.
+    /// `Drain` and `PowerDown`.
+    pub fn state(mut self, state: NodeState) -> Self {
+        self.state.push(state);
+        self
+    }
+
+    /// Replace the node's scheduling weight.
+    pub fn weight(mut self, weight: i64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+}
+
+// The slurmdb plugin models are written by hand; only the slurm plugin
+// models in `models` are generated.
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AccountsResponse {
     #[serde(default)]
-    pub cpus: Option<i64>,
+    pub meta: Meta,
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub accounts: Vec<Account>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Account {
+    #[serde(default)]
+    pub associations: Vec<AssociationShortInfo>,
     #[serde(default)]
-    pub node_count: Option<i64>,
+    pub coordinators: Vec<CoordinatorInfo>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
     #[serde(default)]
-    pub tasks: Option<i64>,
+    pub organization: Option<String>,
     #[serde(default)]
-    pub het_job_id: Option<i64>,
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationShortInfo {
     #[serde(default)]
-    pub het_job_id_set: Option<String>,
+    pub account: Option<String>,
     #[serde(default)]
-    pub het_job_offset: Option<i64>,
+    pub cluster: Option<String>,
     #[serde(default)]
     pub partition: Option<String>,
     #[serde(default)]
-    pub memory_per_node: Option<i64>,
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct CoordinatorInfo {
     #[serde(default)]
-    pub memory_per_cpu: Option<i64>,
+    pub name: Option<String>,
     #[serde(default)]
-    pub minimum_cpus_per_node: Option<i64>,
+    pub direct: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationsResponse {
     #[serde(default)]
-    pub minimum_tmp_disk_per_node: Option<i64>,
+    pub meta: Meta,
     #[serde(default)]
-    pub preempt_time: Option<i64>,
+    pub errors: Vec<Error>,
     #[serde(default)]
-    pub pre_sus_time: Option<i64>,
+    pub associations: Vec<Association>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Association {
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub cluster: Option<String>,
+    #[serde(default)]
+    pub default: Option<AssociationDefault>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub max: Option<AssociationMax>,
+    #[serde(default)]
+    pub min: Option<AssociationMin>,
+    #[serde(default)]
+    pub parent_account: Option<String>,
+    #[serde(default)]
+    pub partition: Option<String>,
     #[serde(default)]
     pub priority: Option<i64>,
     #[serde(default)]
-    pub profile: Option<Vec<String>>,
+    pub qos: Vec<String>,
+    #[serde(default)]
+    pub shares_raw: Option<i64>,
+    #[serde(default)]
+    pub usage: Option<AssociationUsage>,
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationDefault {
     #[serde(default)]
     pub qos: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMax {
     #[serde(default)]
-    pub reboot: Option<bool>,
+    pub jobs: Option<AssociationMaxJobs>,
     #[serde(default)]
-    pub required_nodes: Option<String>,
+    pub tres: Option<AssociationMaxTres>,
     #[serde(default)]
-    pub requeue: Option<bool>,
+    pub per: Option<AssociationMaxPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxJobs {
     #[serde(default)]
-    pub resize_time: Option<i64>,
+    pub per: Option<AssociationMaxJobsPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxJobsPer {
     #[serde(default)]
-    pub restart_cnt: Option<i64>,
+    pub count: Option<i64>,
     #[serde(default)]
-    pub resv_name: Option<String>,
+    pub accruing: Option<i64>,
     #[serde(default)]
-    pub shared: Option<String>,
+    pub submitted: Option<i64>,
     #[serde(default)]
-    pub show_flags: Option<Vec<String>>,
+    pub wall_clock: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxTres {
     #[serde(default)]
-    pub sockets_per_board: Option<i64>,
+    pub total: Vec<TresItem>,
     #[serde(default)]
-    pub sockets_per_node: Option<i64>,
+    pub group: Option<AssociationMaxTresGroup>,
     #[serde(default)]
-    pub start_time: Option<i64>,
+    pub minutes: Option<AssociationMaxTresMinutes>,
     #[serde(default)]
-    pub state_description: Option<String>,
+    pub per: Option<AssociationMaxTresPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxTresGroup {
     #[serde(default)]
-    pub state_reason: Option<String>,
+    pub minutes: Vec<TresItem>,
     #[serde(default)]
-    pub standard_error: Option<String>,
+    pub active: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxTresMinutes {
+    #[serde(default)]
+    pub per: Option<AssociationMaxTresMinutesPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxTresMinutesPer {
+    #[serde(default)]
+    pub job: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxTresPer {
+    #[serde(default)]
+    pub job: Vec<TresItem>,
+    #[serde(default)]
+    pub node: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxPer {
+    #[serde(default)]
+    pub account: Option<AssociationMaxPerAccount>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMaxPerAccount {
+    #[serde(default)]
+    pub wall_clock: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationMin {
+    #[serde(default)]
+    pub priority_threshold: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssociationUsage {
+    #[serde(default)]
+    pub accrue_job_count: Option<i64>,
+    #[serde(default)]
+    pub group_used_wallclock: Option<f64>,
+    #[serde(default)]
+    pub fairshare_factor: Option<f64>,
+    #[serde(default)]
+    pub fairshare_shares: Option<i64>,
+    #[serde(default)]
+    pub normalized_priority: Option<i64>,
+    #[serde(default)]
+    pub normalized_shares: Option<f64>,
+    #[serde(default)]
+    pub effective_normalized_usage: Option<f64>,
+    #[serde(default)]
+    pub raw_usage: Option<i64>,
+    #[serde(default)]
+    pub job_count: Option<i64>,
+    #[serde(default)]
+    pub fairshare_level: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ClustersResponse {
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub clusters: Vec<Cluster>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Cluster {
     #[serde(default)]
-    pub standard_input: Option<String>,
+    pub controller: Option<ClusterController>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub nodes: Option<String>,
+    #[serde(default)]
+    pub select_plugin: Option<String>,
+    #[serde(default)]
+    pub associations: Option<ClusterAssociations>,
+    #[serde(default)]
+    pub rpc_version: Option<i64>,
+    #[serde(default)]
+    pub tres: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ClusterController {
+    #[serde(default)]
+    pub host: Option<String>,
+    #[serde(default)]
+    pub port: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ClusterAssociations {
+    #[serde(default)]
+    pub root: Option<AssociationShortInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosResponse {
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub qos: Vec<Qos>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Qos {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub limits: Option<QosLimits>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub preempt: Option<QosPreempt>,
+    #[serde(default)]
+    pub priority: Option<i64>,
+    #[serde(default)]
+    pub usage_factor: Option<f64>,
+    #[serde(default)]
+    pub usage_threshold: Option<f64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimits {
+    #[serde(default)]
+    pub grace_time: Option<i64>,
+    #[serde(default)]
+    pub max: Option<QosLimitsMax>,
+    #[serde(default)]
+    pub factor: Option<f64>,
+    #[serde(default)]
+    pub min: Option<QosLimitsMin>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMax {
+    #[serde(default)]
+    pub active_jobs: Option<QosLimitsMaxActiveJobs>,
+    #[serde(default)]
+    pub tres: Option<QosLimitsMaxTres>,
+    #[serde(default)]
+    pub wall_clock: Option<QosLimitsMaxWallClock>,
+    #[serde(default)]
+    pub jobs: Option<QosLimitsMaxJobs>,
+    #[serde(default)]
+    pub accruing: Option<QosLimitsMaxAccruing>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxActiveJobs {
+    #[serde(default)]
+    pub accruing: Option<i64>,
+    #[serde(default)]
+    pub count: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxTres {
+    #[serde(default)]
+    pub total: Vec<TresItem>,
+    #[serde(default)]
+    pub minutes: Option<QosLimitsMaxTresMinutes>,
+    #[serde(default)]
+    pub per: Option<QosLimitsMaxTresPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxTresMinutes {
+    #[serde(default)]
+    pub per: Option<QosLimitsMaxTresPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxTresPer {
+    #[serde(default)]
+    pub account: Vec<TresItem>,
+    #[serde(default)]
+    pub job: Vec<TresItem>,
+    #[serde(default)]
+    pub node: Vec<TresItem>,
+    #[serde(default)]
+    pub user: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxWallClock {
+    #[serde(default)]
+    pub per: Option<QosLimitsMaxWallClockPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxWallClockPer {
+    #[serde(default)]
+    pub qos: Option<i64>,
+    #[serde(default)]
+    pub job: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxJobs {
+    #[serde(default)]
+    pub active_jobs: Option<QosLimitsMaxPerAccountUser>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxAccruing {
     #[serde(default)]
-    pub standard_output: Option<String>,
+    pub per: Option<QosLimitsMaxPerAccountUserCounts>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxPerAccountUser {
     #[serde(default)]
-    pub submit_time: Option<i64>,
+    pub per: Option<QosLimitsMaxPerAccountUserCounts>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMaxPerAccountUserCounts {
     #[serde(default)]
-    pub suspend_time: Option<i64>,
+    pub account: Option<i64>,
     #[serde(default)]
-    pub system_comment: Option<String>,
+    pub user: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMin {
     #[serde(default)]
-    pub time_limit: Option<i64>,
+    pub priority_threshold: Option<i64>,
     #[serde(default)]
-    pub time_minimum: Option<i64>,
+    pub tres: Option<QosLimitsMinTres>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMinTres {
     #[serde(default)]
-    pub threads_per_core: Option<i64>,
+    pub per: Option<QosLimitsMinTresPer>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosLimitsMinTresPer {
     #[serde(default)]
-    pub tres_bind: Option<String>,
+    pub job: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QosPreempt {
     #[serde(default)]
-    pub tres_freq: Option<String>,
+    pub list: Vec<String>,
     #[serde(default)]
-    pub tres_per_job: Option<String>,
+    pub mode: Vec<String>,
     #[serde(default)]
-    pub tres_per_node: Option<String>,
+    pub exempt_time: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TresResponse {
     #[serde(default)]
-    pub tres_per_socket: Option<String>,
+    pub meta: Meta,
     #[serde(default)]
-    pub tres_per_task: Option<String>,
+    pub errors: Vec<Error>,
     #[serde(default)]
-    pub tres_req_str: Option<String>,
+    pub tres: Vec<TresItem>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TresItem {
+    #[serde(default, rename = "type")]
+    pub tres_type: Option<String>,
     #[serde(default)]
-    pub tres_alloc_str: Option<String>,
+    pub name: Option<String>,
     #[serde(default)]
-    pub user_id: Option<i64>,
+    pub id: Option<i64>,
     #[serde(default)]
-    pub user_name: Option<String>,
+    pub count: Option<i64>,
+    // Only set for TRES usage reported on job steps
     #[serde(default)]
-    pub wckey: Option<String>,
+    pub node: Option<String>,
     #[serde(default)]
-    pub current_working_directory: Option<String>,
+    pub task: Option<i64>,
 }
 
-#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct JobResources {
-    #[serde(default)]
-    pub nodes: Option<String>,
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct UsersResponse {
     #[serde(default)]
-    pub allocated_cpus: Option<i64>,
+    pub meta: Meta,
     #[serde(default)]
-    pub allocated_hosts: Option<i64>,
+    pub errors: Vec<Error>,
     #[serde(default)]
-    pub allocated_nodes: Option<NodeAllocation>,
+    pub users: Vec<User>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct NodeAllocation {
+pub struct User {
     #[serde(default)]
-    pub memory: Option<i64>,
+    pub administrator_level: Option<String>,
     #[serde(default)]
-    pub cpus: Option<i64>,
+    pub associations: Vec<AssociationShortInfo>,
+    #[serde(default)]
+    pub coordinators: Vec<CoordinatorInfo>,
     #[serde(default)]
-    pub sockets: Option<NodeAllocationSockets>,
+    pub default: Option<UserDefault>,
     #[serde(default)]
-    pub nodename: Option<String>,
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct NodeAllocationSockets {
+pub struct UserDefault {
+    #[serde(default)]
+    pub account: Option<String>,
     #[serde(default)]
-    // wtf? https://slurm.schedmd.com/rest_api.html#v0_0_38_node_allocation_sockets
-    pub cores: Option<i64>,
+    pub wckey: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct ReservationsResponse {
+pub struct WckeysResponse {
     #[serde(default)]
     pub meta: Meta,
     #[serde(default)]
     pub errors: Vec<Error>,
     #[serde(default)]
-    pub reservation: Vec<Reservation>,
+    pub wckeys: Vec<Wckey>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Reservation {
+pub struct Wckey {
     #[serde(default)]
-    pub accounts: Option<String>,
+    pub accounts: Vec<String>,
     #[serde(default)]
-    pub burst_buffer: Option<String>,
-    #[serde(default)]
-    pub core_count: Option<i64>,
+    pub cluster: Option<String>,
     #[serde(default)]
-    pub core_spec_cnt: Option<i64>,
+    pub id: Option<i64>,
     #[serde(default)]
-    pub end_time: Option<i64>,
+    pub name: Option<String>,
     #[serde(default)]
-    pub features: Option<String>,
+    pub user: Option<String>,
     #[serde(default)]
     pub flags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbConfig {
     #[serde(default)]
-    pub groups: Option<String>,
-    #[serde(default)]
-    pub licenses: Option<String>,
-    #[serde(default)]
-    pub max_start_delay: Option<i64>,
-    #[serde(default)]
-    pub name: Option<String>,
-    #[serde(default)]
-    pub node_count: Option<i64>,
-    #[serde(default)]
-    pub node_list: Option<String>,
+    pub meta: Meta,
     #[serde(default)]
-    pub partition: Option<String>,
+    pub errors: Vec<Error>,
     #[serde(default)]
-    pub purge_completed: ReservationPurgeCompleted,
+    pub associations: Vec<Association>,
     #[serde(default)]
-    pub start_time: Option<i64>,
+    pub users: Vec<User>,
     #[serde(default)]
-    pub watts: Option<i64>,
+    pub tres: Vec<TresItem>,
     #[serde(default)]
-    pub tres: Option<String>,
+    pub accounts: Vec<Account>,
     #[serde(default)]
-    pub users: Option<String>,
-}
-
-#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct ReservationPurgeCompleted {
+    pub qos: Vec<Qos>,
     #[serde(default)]
-    time: Option<i64>,
+    pub wckeys: Vec<Wckey>,
 }
 
 #[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Diag {
+pub struct DbDiag {
     #[serde(default)]
     pub meta: Meta,
     #[serde(default)]
     pub errors: Vec<Error>,
     #[serde(default)]
-    pub statistics: DiagStatistics,
+    pub statistics: DbDiagStatistics,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct DiagStatistics {
+pub struct DbDiagStatistics {
+    #[serde(default)]
+    pub time_start: Option<i64>,
     #[serde(default)]
-    pub parts_packed: Option<i64>,
+    pub rollups: Vec<DbDiagRollup>,
+    #[serde(default, rename = "RPCs")]
+    pub rpcs: Vec<DbDiagRpc>,
     #[serde(default)]
-    pub req_time: Option<i64>,
+    pub users: Vec<DbDiagUser>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbDiagRollup {
+    #[serde(default, rename = "type")]
+    pub rollup_type: Option<String>,
     #[serde(default)]
-    pub req_time_start: Option<i64>,
+    pub last_run: Option<i64>,
     #[serde(default)]
-    pub server_thread_count: Option<i64>,
+    pub last_cycle: Option<i64>,
     #[serde(default)]
-    pub agent_queue_size: Option<i64>,
+    pub max_cycle: Option<i64>,
     #[serde(default)]
-    pub agent_count: Option<i64>,
+    pub total_time: Option<i64>,
     #[serde(default)]
-    pub agent_thread_count: Option<i64>,
+    pub mean_cycles: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbDiagRpc {
     #[serde(default)]
-    pub dbd_agent_queue_size: Option<i64>,
+    pub rpc: Option<String>,
     #[serde(default)]
-    pub gettimeofday_latency: Option<i64>,
+    pub count: Option<i64>,
     #[serde(default)]
-    pub schedule_cycle_max: Option<i64>,
+    pub time: Option<DbDiagTime>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbDiagUser {
     #[serde(default)]
-    pub schedule_cycle_last: Option<i64>,
+    pub user: Option<String>,
     #[serde(default)]
-    pub schedule_cycle_total: Option<i64>,
+    pub count: Option<i64>,
     #[serde(default)]
-    pub schedule_cycle_mean: Option<i64>,
+    pub time: Option<DbDiagTime>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbDiagTime {
     #[serde(default)]
-    pub schedule_cycle_mean_depth: Option<i64>,
+    pub average: Option<i64>,
     #[serde(default)]
-    pub schedule_cycle_per_minute: Option<i64>,
+    pub total: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobsResponse {
     #[serde(default)]
-    pub schedule_queue_length: Option<i64>,
+    pub meta: Meta,
     #[serde(default)]
-    pub jobs_submitted: Option<i64>,
+    pub errors: Vec<Error>,
     #[serde(default)]
-    pub jobs_started: Option<i64>,
+    pub jobs: Vec<DbJob>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJob {
     #[serde(default)]
-    pub jobs_completed: Option<i64>,
+    pub account: Option<String>,
     #[serde(default)]
-    pub jobs_canceled: Option<i64>,
+    pub comment: Option<DbJobComment>,
     #[serde(default)]
-    pub jobs_failed: Option<i64>,
+    pub allocation_nodes: Option<i64>,
     #[serde(default)]
-    pub jobs_pending: Option<i64>,
+    pub array: Option<DbJobArray>,
     #[serde(default)]
-    pub jobs_running: Option<i64>,
+    pub association: Option<AssociationShortInfo>,
     #[serde(default)]
-    pub job_states_ts: Option<i64>,
+    pub cluster: Option<String>,
     #[serde(default)]
-    pub bf_backfilled_jobs: Option<i64>,
+    pub constraints: Option<String>,
     #[serde(default)]
-    pub bf_last_backfilled_jobs: Option<i64>,
+    pub container: Option<String>,
     #[serde(default)]
-    pub bf_backfilled_het_jobs: Option<i64>,
+    pub derived_exit_code: Option<DbExitCode>,
     #[serde(default)]
-    pub bf_cycle_counter: Option<i64>,
+    pub time: Option<DbJobTime>,
     #[serde(default)]
-    pub bf_cycle_mean: Option<i64>,
+    pub exit_code: Option<DbExitCode>,
     #[serde(default)]
-    pub bf_cycle_max: Option<i64>,
+    pub flags: Vec<String>,
     #[serde(default)]
-    pub bf_last_depth: Option<i64>,
+    pub group: Option<String>,
     #[serde(default)]
-    pub bf_last_depth_try: Option<i64>,
+    pub het: Option<DbJobHet>,
     #[serde(default)]
-    pub bf_depth_mean: Option<i64>,
+    pub job_id: Option<i64>,
     #[serde(default)]
-    pub bf_depth_mean_try: Option<i64>,
+    pub name: Option<String>,
     #[serde(default)]
-    pub bf_cycle_last: Option<i64>,
+    pub mcs: Option<DbJobMcs>,
     #[serde(default)]
-    pub bf_queue_len: Option<i64>,
+    pub nodes: Option<String>,
     #[serde(default)]
-    pub bf_queue_len_mean: Option<i64>,
+    pub partition: Option<String>,
     #[serde(default)]
-    pub bf_table_size: Option<i64>,
+    pub priority: Option<i64>,
     #[serde(default)]
-    pub bf_table_size_mean: Option<i64>,
+    pub qos: Option<String>,
     #[serde(default)]
-    pub bf_when_last_cycle: Option<i64>,
+    pub required: Option<DbJobRequired>,
     #[serde(default)]
-    pub bf_active: Option<bool>,
+    pub kill_request_user: Option<String>,
     #[serde(default)]
-    pub rpcs_by_message_type: Option<Vec<DiagRpcm>>,
+    pub reservation: Option<DbJobReservation>,
     #[serde(default)]
-    pub rpcs_by_user: Option<Vec<DiagRpcu>>,
-}
-
-#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct DiagRpcm {
+    pub state: Option<DbJobState>,
     #[serde(default)]
-    pub message_type: Option<String>,
+    pub steps: Vec<DbJobStep>,
     #[serde(default)]
-    pub type_id: Option<i64>,
+    pub tres: Option<DbJobTres>,
     #[serde(default)]
-    pub count: Option<i64>,
+    pub user: Option<String>,
     #[serde(default)]
-    pub average_time: Option<i64>,
+    pub wckey: Option<DbJobWckey>,
     #[serde(default)]
-    pub total_time: Option<i64>,
+    pub working_directory: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct DiagRpcu {
+pub struct DbJobComment {
     #[serde(default)]
-    pub user: Option<String>,
-    #[serde(default)]
-    pub user_id: Option<i64>,
-    #[serde(default)]
-    pub count: Option<i64>,
+    pub administrator: Option<String>,
     #[serde(default)]
-    pub average_time: Option<i64>,
+    pub job: Option<String>,
     #[serde(default)]
-    pub total_time: Option<i64>,
+    pub system: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct NodesResponse {
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobArray {
     #[serde(default)]
-    pub meta: Meta,
+    pub job_id: Option<i64>,
     #[serde(default)]
-    pub errors: Vec<Error>,
+    pub task: Option<String>,
     #[serde(default)]
-    pub nodes: Vec<Node>,
+    pub task_id: Option<i64>,
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Node {
-    #[serde(default)]
-    pub architecture: Option<String>,
-    #[serde(default)]
-    pub burstbuffer_network_address: Option<String>,
-    #[serde(default)]
-    pub boards: Option<i64>,
-    #[serde(default)]
-    pub boot_time: Option<i64>,
-    #[serde(default)]
-    pub cores: Option<i64>,
-    #[serde(default)]
-    pub cpu_binding: Option<i64>,
-    #[serde(default)]
-    pub cpu_load: Option<i64>,
-    #[serde(default)]
-    pub free_memory: Option<i64>,
-    #[serde(default)]
-    pub cpus: Option<i64>,
-    #[serde(default)]
-    pub features: Option<String>,
-    #[serde(default)]
-    pub active_features: Option<String>,
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbExitCode {
     #[serde(default)]
-    pub gres: Option<String>,
+    pub status: Option<String>,
     #[serde(default)]
-    pub gres_drained: Option<String>,
+    pub return_code: Option<i64>,
     #[serde(default)]
-    pub gres_used: Option<String>,
+    pub signal: Option<DbExitCodeSignal>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbExitCodeSignal {
     #[serde(default)]
-    pub mcs_label: Option<String>,
+    pub signal_id: Option<i64>,
     #[serde(default)]
     pub name: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobTime {
+    /// Wall clock time in seconds.
     #[serde(default)]
-    pub next_state_after_reboot: Option<String>,
-    #[serde(default)]
-    pub next_state_after_reboot_flags: Vec<String>,
-    #[serde(default)]
-    pub address: Option<String>,
-    #[serde(default)]
-    pub hostname: Option<String>,
-    #[serde(default)]
-    pub state: Option<String>,
+    pub elapsed: Option<i64>,
     #[serde(default)]
-    pub state_flags: Vec<String>,
+    pub eligible: Option<i64>,
     #[serde(default)]
-    pub operating_system: Option<String>,
+    pub end: Option<i64>,
     #[serde(default)]
-    pub owner: Option<String>,
+    pub start: Option<i64>,
     #[serde(default)]
-    pub partitions: Vec<String>,
+    pub submission: Option<i64>,
     #[serde(default)]
-    pub port: Option<i64>,
+    pub suspended: Option<i64>,
+    /// Time limit in minutes.
     #[serde(default)]
-    pub real_memory: Option<i64>,
+    pub limit: Option<i64>,
+    /// Total CPU time, user plus system.
     #[serde(default)]
-    pub reason: Option<String>,
+    pub total: Option<DbCpuTime>,
     #[serde(default)]
-    pub reason_changed_at: Option<i64>,
+    pub user: Option<DbCpuTime>,
     #[serde(default)]
-    pub reason_set_by_user: Option<String>,
+    pub system: Option<DbCpuTime>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbCpuTime {
     #[serde(default)]
-    pub slurmd_start_time: Option<i64>,
+    pub seconds: Option<i64>,
     #[serde(default)]
-    pub sockets: Option<i64>,
+    pub microseconds: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobHet {
     #[serde(default)]
-    pub threads: Option<i64>,
+    pub job_id: Option<i64>,
     #[serde(default)]
-    pub temporary_disk: Option<i64>,
+    pub job_offset: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobMcs {
     #[serde(default)]
-    pub weight: Option<i64>,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobRequired {
+    #[serde(default, rename = "CPUs")]
+    pub cpus: Option<i64>,
     #[serde(default)]
-    pub tres: Option<String>,
+    pub memory: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobReservation {
     #[serde(default)]
-    pub tres_used: Option<String>,
+    pub id: Option<i64>,
     #[serde(default)]
-    pub tres_weighted: Option<f64>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobState {
     #[serde(default)]
-    pub slurmd_version: Option<String>,
+    pub current: Option<String>,
     #[serde(default)]
-    pub alloc_cpus: Option<i64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobTres {
     #[serde(default)]
-    pub idle_cpus: Option<i64>,
+    pub allocated: Vec<TresItem>,
     #[serde(default)]
-    pub alloc_memory: Option<i64>,
+    pub requested: Vec<TresItem>,
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct PartitionsResponse {
-    #[serde(default)]
-    pub meta: Meta,
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobWckey {
     #[serde(default)]
-    pub errors: Vec<Error>,
+    pub wckey: Option<String>,
     #[serde(default)]
-    pub partitions: Vec<Partition>,
+    pub flags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Partition {
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStep {
     #[serde(default)]
-    pub flags: Vec<String>,
+    pub time: Option<DbJobTime>,
     #[serde(default)]
-    pub preemption_mode: Vec<String>,
+    pub exit_code: Option<DbExitCode>,
     #[serde(default)]
-    pub allowed_allocation_nodes: String,
-    // #[serde(default)]
-    pub allowed_accounts: String,
+    pub nodes: Option<DbJobStepNodes>,
     #[serde(default)]
-    pub allowed_groups: String,
+    pub tasks: Option<DbJobStepTasks>,
     #[serde(default)]
-    pub allowed_qos: String,
+    pub pid: Option<String>,
+    #[serde(default, rename = "CPU")]
+    pub cpu: Option<DbJobStepCpu>,
     #[serde(default)]
-    pub alternative: String,
+    pub kill_request_user: Option<String>,
     #[serde(default)]
-    pub billing_weights: String,
+    pub state: Option<String>,
     #[serde(default)]
-    pub default_memory_per_cpu: Option<i64>,
+    pub statistics: Option<DbJobStepStatistics>,
     #[serde(default)]
-    pub default_time_limit: Option<i64>,
+    pub step: Option<DbJobStepId>,
     #[serde(default)]
-    pub denied_accounts: String,
+    pub task: Option<DbJobStepTask>,
     #[serde(default)]
-    pub denied_qos: String,
+    pub tres: Option<DbJobStepTres>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepNodes {
     #[serde(default)]
-    pub preemption_grace_time: Option<i64>,
+    pub count: Option<i64>,
     #[serde(default)]
-    pub maximum_cpus_per_node: Option<i64>,
+    pub range: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepTasks {
     #[serde(default)]
-    pub maximum_memory_per_node: Option<i64>,
-    // #[serde(default)]
-    pub maximum_nodes_per_job: Option<i64>,
+    pub count: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepCpu {
     #[serde(default)]
-    pub max_time_limit: Option<i64>,
+    pub requested_frequency: Option<DbJobStepCpuFrequency>,
     #[serde(default)]
-    pub min_nodes_per_job: Option<i64>,
+    pub governor: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepCpuFrequency {
     #[serde(default)]
-    pub name: String,
+    pub min: Option<i64>,
     #[serde(default)]
-    pub nodes: String,
+    pub max: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepStatistics {
+    #[serde(default, rename = "CPU")]
+    pub cpu: Option<DbJobStepStatisticsCpu>,
     #[serde(default)]
-    pub over_time_limit: Option<i64>,
+    pub energy: Option<DbJobStepStatisticsEnergy>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepStatisticsCpu {
     #[serde(default)]
-    pub priority_job_factor: Option<i64>,
-    // #[serde(default)]
-    pub priority_tier: Option<i64>,
+    pub actual_frequency: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepStatisticsEnergy {
     #[serde(default)]
-    pub qos: String,
+    pub consumed: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepId {
     #[serde(default)]
-    pub state: String,
+    pub job_id: Option<i64>,
     #[serde(default)]
-    pub total_cpus: Option<i64>,
+    pub het: Option<DbJobStepHet>,
     #[serde(default)]
-    pub total_nodes: Option<i64>,
+    pub id: Option<String>,
     #[serde(default)]
-    pub tres: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepHet {
     #[serde(default)]
-    pub maximum_memory_per_cpu: Option<i64>,
+    pub component: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepTask {
     #[serde(default)]
-    pub default_memory_per_node: Option<i64>,
+    pub distribution: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Pings {
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepTres {
     #[serde(default)]
-    pub meta: Meta,
+    pub requested: Option<DbJobStepTresUsage>,
+    /// TRES actually used by the step.
     #[serde(default)]
-    pub errors: Vec<Error>,
+    pub consumed: Option<DbJobStepTresUsage>,
     #[serde(default)]
-    pub pings: Vec<Ping>,
+    pub allocated: Vec<TresItem>,
 }
 
-#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
-pub struct Ping {
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DbJobStepTresUsage {
     #[serde(default)]
-    pub hostname: String,
+    pub max: Vec<TresItem>,
     #[serde(default)]
-    pub ping: String,
+    pub min: Vec<TresItem>,
     #[serde(default)]
-    pub mode: String,
+    pub average: Vec<TresItem>,
     #[serde(default)]
-    pub status: i32,
+    pub total: Vec<TresItem>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
@@ -1080,6 +2370,21 @@ pub struct MetaPlugin {
     pub plugin_type: String,
     #[serde(default)]
     pub name: String,
+    /// Data parser plugin, e.g. `data_parser/v0.0.40`. Only sent by
+    /// `v0.0.40` and later, whose plugin type no longer holds the version.
+    #[serde(default)]
+    pub data_parser: String,
+}
+
+impl Meta {
+    /// Version of the REST API that produced this response, as reported
+    /// by the plugin type or data parser.
+    pub fn api_version(&self) -> Option<ApiVersion> {
+        [&self.plugin.data_parser, &self.plugin.plugin_type]
+            .into_iter()
+            .filter_map(|s| s.rsplit('/').next())
+            .find_map(|v| v.trim_start_matches("db").parse().ok())
+    }
 }
 
 #[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
@@ -1107,3 +2412,125 @@ pub struct Error {
     #[serde(default)]
     pub error_number: i32,
 }
+
+impl Error {
+    /// Name of the Slurm errno, e.g. `ESLURM_INVALID_JOB_ID`, if known.
+    pub fn errno_name(&self) -> Option<&'static str> {
+        errno::name(self.error_number)
+    }
+}
+
+// Response of endpoints that only report success or failure.
+#[derive(Debug, Clone, Deserialize)]
+struct Status {
+    #[serde(default)]
+    errors: Vec<Error>,
+}
+
+/// Implemented by every response that carries Slurm's `errors` array.
+pub trait SlurmResponse {
+    /// Errors Slurm reported alongside the payload.
+    fn errors(&self) -> &[Error];
+
+    /// The reported errors, to change them in place.
+    fn errors_mut(&mut self) -> &mut Vec<Error>;
+
+    /// Take the reported errors out of the response.
+    fn take_errors(&mut self) -> Vec<Error> {
+        std::mem::take(self.errors_mut())
+    }
+}
+
+macro_rules! impl_slurm_response {
+    ($($response:ty),* $(,)?) => {
+        $(
+            impl SlurmResponse for $response {
+                fn errors(&self) -> &[Error] {
+                    &self.errors
+                }
+
+                fn errors_mut(&mut self) -> &mut Vec<Error> {
+                    &mut self.errors
+                }
+            }
+        )*
+    };
+}
+
+impl_slurm_response!(
+    Status,
+    Pings,
+    Diag,
+    Licenses,
+    JobsResponse,
+    JobSubmitResponse,
+    NodesResponse,
+    PartitionsResponse,
+    ReservationsResponse,
+    AccountsResponse,
+    AssociationsResponse,
+    ClustersResponse,
+    QosResponse,
+    TresResponse,
+    UsersResponse,
+    WckeysResponse,
+    DbConfig,
+    DbDiag,
+    DbJobsResponse,
+);
+
+/// Result of asking for data only if it changed since a given time, as
+/// `Slurm::get_jobs_since` and friends do.
+///
+/// `NotModified` is returned when slurmctld reports
+/// `SLURM_NO_CHANGE_IN_DATA`, or, from v0.0.40 on, when the `last_update`
+/// it sends is not after the given time. A list that became empty is
+/// `Modified` like any other change.
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    /// Something changed; this is the complete, current data.
+    Modified(T),
+    /// Nothing changed since the given time.
+    NotModified,
+}
+
+impl<T> Conditional<T> {
+    fn new(response: T, unchanged: bool) -> Self {
+        if unchanged {
+            Conditional::NotModified
+        } else {
+            Conditional::Modified(response)
+        }
+    }
+
+    /// The data, if it changed.
+    pub fn modified(self) -> Option<T> {
+        match self {
+            Conditional::Modified(response) => Some(response),
+            Conditional::NotModified => None,
+        }
+    }
+}
+
+/// What to do when a response comes back with a 200 but Slurm still
+/// reported errors, e.g. asking for a partition that does not exist.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Turn any reported error into `SlurmError::Slurm`.
+    #[default]
+    Strict,
+    /// Return the response anyway and leave the errors in its `errors`
+    /// field as warnings for the caller to inspect.
+    Lenient,
+}
+
+impl ErrorPolicy {
+    fn check<T: SlurmResponse>(&self, mut response: T) -> Result<T> {
+        match self {
+            ErrorPolicy::Strict if !response.errors().is_empty() => Err(SlurmError::Slurm {
+                errors: response.take_errors(),
+            }),
+            _ => Ok(response),
+        }
+    }
+}
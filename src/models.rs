@@ -0,0 +1,708 @@
+//! Response models generated from `openapi/models.json`.
+//!
+//! @generated by `cargo run -p slurm-rs-codegen`, do not edit by hand.
+use crate::{Error, Meta};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_diag>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Diag {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub statistics: Option<DiagStatistics>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_diag_rpcm>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DiagRpcm {
+    #[serde(default)]
+    pub average_time: Option<i64>,
+    #[serde(default)]
+    pub count: Option<i64>,
+    #[serde(default)]
+    pub message_type: Option<String>,
+    #[serde(default)]
+    pub total_time: Option<i64>,
+    #[serde(default)]
+    pub type_id: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_diag_rpcu>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DiagRpcu {
+    #[serde(default)]
+    pub average_time: Option<i64>,
+    #[serde(default)]
+    pub count: Option<i64>,
+    #[serde(default)]
+    pub total_time: Option<i64>,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_diag_statistics>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DiagStatistics {
+    #[serde(default)]
+    pub agent_count: Option<i64>,
+    #[serde(default)]
+    pub agent_queue_size: Option<i64>,
+    #[serde(default)]
+    pub agent_thread_count: Option<i64>,
+    #[serde(default)]
+    pub bf_active: Option<bool>,
+    #[serde(default)]
+    pub bf_backfilled_het_jobs: Option<i64>,
+    #[serde(default)]
+    pub bf_backfilled_jobs: Option<i64>,
+    #[serde(default)]
+    pub bf_cycle_counter: Option<i64>,
+    #[serde(default)]
+    pub bf_cycle_last: Option<i64>,
+    #[serde(default)]
+    pub bf_cycle_max: Option<i64>,
+    #[serde(default)]
+    pub bf_cycle_mean: Option<i64>,
+    #[serde(default)]
+    pub bf_depth_mean: Option<i64>,
+    #[serde(default)]
+    pub bf_depth_mean_try: Option<i64>,
+    #[serde(default)]
+    pub bf_last_backfilled_jobs: Option<i64>,
+    #[serde(default)]
+    pub bf_last_depth: Option<i64>,
+    #[serde(default)]
+    pub bf_last_depth_try: Option<i64>,
+    #[serde(default)]
+    pub bf_queue_len: Option<i64>,
+    #[serde(default)]
+    pub bf_queue_len_mean: Option<i64>,
+    #[serde(default)]
+    pub bf_table_size: Option<i64>,
+    #[serde(default)]
+    pub bf_table_size_mean: Option<i64>,
+    #[serde(default)]
+    pub bf_when_last_cycle: Option<i64>,
+    #[serde(default)]
+    pub dbd_agent_queue_size: Option<i64>,
+    #[serde(default)]
+    pub gettimeofday_latency: Option<i64>,
+    #[serde(default)]
+    pub job_states_ts: Option<i64>,
+    #[serde(default)]
+    pub jobs_canceled: Option<i64>,
+    #[serde(default)]
+    pub jobs_completed: Option<i64>,
+    #[serde(default)]
+    pub jobs_failed: Option<i64>,
+    #[serde(default)]
+    pub jobs_pending: Option<i64>,
+    #[serde(default)]
+    pub jobs_running: Option<i64>,
+    #[serde(default)]
+    pub jobs_started: Option<i64>,
+    #[serde(default)]
+    pub jobs_submitted: Option<i64>,
+    #[serde(default)]
+    pub parts_packed: Option<i64>,
+    #[serde(default)]
+    pub req_time: Option<i64>,
+    #[serde(default)]
+    pub req_time_start: Option<i64>,
+    #[serde(default)]
+    pub rpcs_by_message_type: Vec<DiagRpcm>,
+    #[serde(default)]
+    pub rpcs_by_user: Vec<DiagRpcu>,
+    #[serde(default)]
+    pub schedule_cycle_last: Option<i64>,
+    #[serde(default)]
+    pub schedule_cycle_max: Option<i64>,
+    #[serde(default)]
+    pub schedule_cycle_mean: Option<i64>,
+    #[serde(default)]
+    pub schedule_cycle_mean_depth: Option<i64>,
+    #[serde(default)]
+    pub schedule_cycle_per_minute: Option<i64>,
+    #[serde(default)]
+    pub schedule_cycle_total: Option<i64>,
+    #[serde(default)]
+    pub schedule_queue_length: Option<i64>,
+    #[serde(default)]
+    pub server_thread_count: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_job_resources>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct JobResources {
+    #[serde(default)]
+    pub allocated_cpus: Option<i64>,
+    #[serde(default)]
+    pub allocated_hosts: Option<i64>,
+    #[serde(default)]
+    pub allocated_nodes: Option<NodeAllocation>,
+    #[serde(default)]
+    pub nodes: Option<String>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_job_response_properties>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct JobResponseProperties {
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub accrue_time: Option<i64>,
+    #[serde(default)]
+    pub admin_comment: Option<String>,
+    #[serde(default)]
+    pub array_job_id: Option<i64>,
+    #[serde(default)]
+    pub array_max_tasks: Option<i64>,
+    #[serde(default)]
+    pub array_task_id: Option<i64>,
+    #[serde(default)]
+    pub array_task_string: Option<String>,
+    #[serde(default)]
+    pub association_id: Option<i64>,
+    #[serde(default)]
+    pub batch_features: Option<String>,
+    #[serde(default)]
+    pub batch_flag: Option<bool>,
+    #[serde(default)]
+    pub batch_host: Option<String>,
+    #[serde(default)]
+    pub billable_tres: Option<f64>,
+    #[serde(default)]
+    pub burst_buffer: Option<String>,
+    #[serde(default)]
+    pub burst_buffer_state: Option<String>,
+    #[serde(default)]
+    pub cluster: Option<String>,
+    #[serde(default)]
+    pub cluster_features: Option<String>,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub container: Option<String>,
+    #[serde(default)]
+    pub contiguous: Option<bool>,
+    #[serde(default)]
+    pub core_spec: Option<String>,
+    #[serde(default)]
+    pub cores_per_socket: Option<String>,
+    #[serde(default)]
+    pub cpu_frequency_governor: Option<String>,
+    #[serde(default)]
+    pub cpu_frequency_maximum: Option<String>,
+    #[serde(default)]
+    pub cpu_frequency_minimum: Option<String>,
+    #[serde(default)]
+    pub cpus: Option<i64>,
+    #[serde(default)]
+    pub cpus_per_task: Option<String>,
+    #[serde(default)]
+    pub cpus_per_tres: Option<String>,
+    #[serde(default)]
+    pub current_working_directory: Option<String>,
+    #[serde(default)]
+    pub deadline: Option<i64>,
+    #[serde(default)]
+    pub delay_boot: Option<i64>,
+    #[serde(default)]
+    pub dependency: Option<String>,
+    #[serde(default)]
+    pub derived_exit_code: Option<i64>,
+    #[serde(default)]
+    pub eligible_time: Option<i64>,
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    #[serde(default)]
+    pub excluded_nodes: Option<String>,
+    #[serde(default)]
+    pub exit_code: Option<i64>,
+    #[serde(default)]
+    pub features: Option<String>,
+    #[serde(default)]
+    pub federation_origin: Option<String>,
+    #[serde(default)]
+    pub federation_siblings_active: Option<String>,
+    #[serde(default)]
+    pub federation_siblings_viable: Option<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub gres_detail: Vec<String>,
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    #[serde(default)]
+    pub het_job_id: Option<i64>,
+    #[serde(default)]
+    pub het_job_id_set: Option<String>,
+    #[serde(default)]
+    pub het_job_offset: Option<i64>,
+    #[serde(default)]
+    pub job_id: Option<i64>,
+    #[serde(default)]
+    pub job_resources: Option<JobResources>,
+    #[serde(default)]
+    pub job_state: Option<String>,
+    /// Flags sent alongside the state by v0.0.40 and later, e.g.
+    /// COMPLETING. Always empty for older versions.
+    #[serde(default)]
+    pub job_state_flags: Vec<String>,
+    #[serde(default)]
+    pub last_sched_evaluation: Option<i64>,
+    #[serde(default)]
+    pub licenses: Option<String>,
+    #[serde(default)]
+    pub max_cpus: Option<i64>,
+    #[serde(default)]
+    pub max_nodes: Option<i64>,
+    #[serde(default)]
+    pub mcs_label: Option<String>,
+    #[serde(default)]
+    pub memory_per_cpu: Option<i64>,
+    #[serde(default)]
+    pub memory_per_node: Option<i64>,
+    #[serde(default)]
+    pub memory_per_tres: Option<String>,
+    #[serde(default)]
+    pub minimum_cpus_per_node: Option<i64>,
+    #[serde(default)]
+    pub minimum_tmp_disk_per_node: Option<i64>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub nice: Option<i64>,
+    #[serde(default)]
+    pub node_count: Option<i64>,
+    #[serde(default)]
+    pub nodes: Option<String>,
+    #[serde(default)]
+    pub partition: Option<String>,
+    #[serde(default)]
+    pub pre_sus_time: Option<i64>,
+    #[serde(default)]
+    pub preempt_time: Option<i64>,
+    #[serde(default)]
+    pub priority: Option<i64>,
+    #[serde(default)]
+    pub profile: Vec<String>,
+    #[serde(default)]
+    pub qos: Option<String>,
+    #[serde(default)]
+    pub reboot: Option<bool>,
+    #[serde(default)]
+    pub requeue: Option<bool>,
+    #[serde(default)]
+    pub required_nodes: Option<String>,
+    #[serde(default)]
+    pub resize_time: Option<i64>,
+    #[serde(default)]
+    pub restart_cnt: Option<i64>,
+    #[serde(default)]
+    pub resv_name: Option<String>,
+    #[serde(default)]
+    pub shared: Option<String>,
+    #[serde(default)]
+    pub show_flags: Vec<String>,
+    #[serde(default)]
+    pub sockets_per_board: Option<i64>,
+    #[serde(default)]
+    pub sockets_per_node: Option<i64>,
+    #[serde(default)]
+    pub standard_error: Option<String>,
+    #[serde(default)]
+    pub standard_input: Option<String>,
+    #[serde(default)]
+    pub standard_output: Option<String>,
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub state_description: Option<String>,
+    #[serde(default)]
+    pub state_reason: Option<String>,
+    #[serde(default)]
+    pub submit_time: Option<i64>,
+    #[serde(default)]
+    pub suspend_time: Option<i64>,
+    #[serde(default)]
+    pub system_comment: Option<String>,
+    #[serde(default)]
+    pub tasks: Option<i64>,
+    #[serde(default)]
+    pub tasks_per_board: Option<i64>,
+    #[serde(default)]
+    pub tasks_per_core: Option<i64>,
+    #[serde(default)]
+    pub tasks_per_socket: Option<i64>,
+    #[serde(default)]
+    pub thread_spec: Option<String>,
+    #[serde(default)]
+    pub threads_per_core: Option<i64>,
+    #[serde(default)]
+    pub time_limit: Option<i64>,
+    #[serde(default)]
+    pub time_minimum: Option<i64>,
+    #[serde(default)]
+    pub tres_alloc_str: Option<String>,
+    #[serde(default)]
+    pub tres_bind: Option<String>,
+    #[serde(default)]
+    pub tres_freq: Option<String>,
+    #[serde(default)]
+    pub tres_per_job: Option<String>,
+    #[serde(default)]
+    pub tres_per_node: Option<String>,
+    #[serde(default)]
+    pub tres_per_socket: Option<String>,
+    #[serde(default)]
+    pub tres_per_task: Option<String>,
+    #[serde(default)]
+    pub tres_req_str: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<i64>,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    #[serde(default)]
+    pub wckey: Option<String>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_jobs_response>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct JobsResponse {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub jobs: Vec<JobResponseProperties>,
+    /// When slurmctld last changed this data, in seconds since the epoch.
+    /// Only sent by v0.0.40 and later.
+    #[serde(default)]
+    pub last_update: Option<i64>,
+    #[serde(default)]
+    pub meta: Meta,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_license>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct License {
+    #[serde(default, rename = "Free")]
+    pub free: Option<i64>,
+    #[serde(default, rename = "LicenseName")]
+    pub license_name: Option<String>,
+    #[serde(default, rename = "Remote")]
+    pub remote: Option<bool>,
+    #[serde(default, rename = "Reserved")]
+    pub reserved: Option<i64>,
+    #[serde(default, rename = "Total")]
+    pub total: Option<i64>,
+    #[serde(default, rename = "Used")]
+    pub used: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_licenses>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Licenses {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub licenses: Vec<License>,
+    #[serde(default)]
+    pub meta: Meta,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_node>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Node {
+    #[serde(default)]
+    pub active_features: Option<String>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub alloc_cpus: Option<i64>,
+    #[serde(default)]
+    pub alloc_memory: Option<i64>,
+    #[serde(default)]
+    pub architecture: Option<String>,
+    #[serde(default)]
+    pub boards: Option<i64>,
+    #[serde(default)]
+    pub boot_time: Option<i64>,
+    #[serde(default)]
+    pub burstbuffer_network_address: Option<String>,
+    #[serde(default)]
+    pub cores: Option<i64>,
+    #[serde(default)]
+    pub cpu_binding: Option<i64>,
+    #[serde(default)]
+    pub cpu_load: Option<i64>,
+    #[serde(default)]
+    pub cpus: Option<i64>,
+    #[serde(default)]
+    pub features: Option<String>,
+    #[serde(default)]
+    pub free_memory: Option<i64>,
+    #[serde(default)]
+    pub gres: Option<String>,
+    #[serde(default)]
+    pub gres_drained: Option<String>,
+    #[serde(default)]
+    pub gres_used: Option<String>,
+    #[serde(default)]
+    pub hostname: Option<String>,
+    #[serde(default)]
+    pub idle_cpus: Option<i64>,
+    #[serde(default)]
+    pub mcs_label: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub next_state_after_reboot: Option<String>,
+    #[serde(default)]
+    pub next_state_after_reboot_flags: Vec<String>,
+    #[serde(default)]
+    pub operating_system: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub partitions: Vec<String>,
+    #[serde(default)]
+    pub port: Option<i64>,
+    #[serde(default)]
+    pub real_memory: Option<i64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub reason_changed_at: Option<i64>,
+    #[serde(default)]
+    pub reason_set_by_user: Option<String>,
+    #[serde(default)]
+    pub slurmd_start_time: Option<i64>,
+    #[serde(default)]
+    pub slurmd_version: Option<String>,
+    #[serde(default)]
+    pub sockets: Option<i64>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub state_flags: Vec<String>,
+    #[serde(default)]
+    pub temporary_disk: Option<i64>,
+    #[serde(default)]
+    pub threads: Option<i64>,
+    #[serde(default)]
+    pub tres: Option<String>,
+    #[serde(default)]
+    pub tres_used: Option<String>,
+    #[serde(default)]
+    pub tres_weighted: Option<f64>,
+    #[serde(default)]
+    pub weight: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_node_allocation>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct NodeAllocation {
+    #[serde(default)]
+    pub cpus: Option<i64>,
+    #[serde(default)]
+    pub memory: Option<i64>,
+    #[serde(default)]
+    pub nodename: Option<String>,
+    #[serde(default)]
+    pub sockets: Option<NodeAllocationSockets>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_node_allocation_sockets>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct NodeAllocationSockets {
+    #[serde(default)]
+    pub cores: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_nodes_response>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct NodesResponse {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    /// When slurmctld last changed this data, in seconds since the epoch.
+    /// Only sent by v0.0.40 and later.
+    #[serde(default)]
+    pub last_update: Option<i64>,
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_partition>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Partition {
+    #[serde(default)]
+    pub allowed_accounts: Option<String>,
+    #[serde(default)]
+    pub allowed_allocation_nodes: Option<String>,
+    #[serde(default)]
+    pub allowed_groups: Option<String>,
+    #[serde(default)]
+    pub allowed_qos: Option<String>,
+    #[serde(default)]
+    pub alternative: Option<String>,
+    #[serde(default)]
+    pub billing_weights: Option<String>,
+    #[serde(default)]
+    pub default_memory_per_cpu: Option<i64>,
+    #[serde(default)]
+    pub default_memory_per_node: Option<i64>,
+    #[serde(default)]
+    pub default_time_limit: Option<i64>,
+    #[serde(default)]
+    pub denied_accounts: Option<String>,
+    #[serde(default)]
+    pub denied_qos: Option<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub max_time_limit: Option<i64>,
+    #[serde(default)]
+    pub maximum_cpus_per_node: Option<i64>,
+    #[serde(default)]
+    pub maximum_memory_per_cpu: Option<i64>,
+    #[serde(default)]
+    pub maximum_memory_per_node: Option<i64>,
+    #[serde(default)]
+    pub maximum_nodes_per_job: Option<i64>,
+    #[serde(default)]
+    pub min_nodes_per_job: Option<i64>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub nodes: Option<String>,
+    #[serde(default)]
+    pub over_time_limit: Option<i64>,
+    #[serde(default)]
+    pub preemption_grace_time: Option<i64>,
+    #[serde(default)]
+    pub preemption_mode: Vec<String>,
+    #[serde(default)]
+    pub priority_job_factor: Option<i64>,
+    #[serde(default)]
+    pub priority_tier: Option<i64>,
+    #[serde(default)]
+    pub qos: Option<String>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub total_cpus: Option<i64>,
+    #[serde(default)]
+    pub total_nodes: Option<i64>,
+    #[serde(default)]
+    pub tres: Option<String>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_partitions_response>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct PartitionsResponse {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    /// When slurmctld last changed this data, in seconds since the epoch.
+    /// Only sent by v0.0.40 and later.
+    #[serde(default)]
+    pub last_update: Option<i64>,
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub partitions: Vec<Partition>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_ping>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Ping {
+    #[serde(default)]
+    pub hostname: Option<String>,
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub ping: Option<String>,
+    #[serde(default)]
+    pub status: Option<i32>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_pings>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Pings {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub pings: Vec<Ping>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_reservation>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct Reservation {
+    #[serde(default)]
+    pub accounts: Option<String>,
+    #[serde(default)]
+    pub burst_buffer: Option<String>,
+    #[serde(default)]
+    pub core_count: Option<i64>,
+    #[serde(default)]
+    pub core_spec_cnt: Option<i64>,
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    #[serde(default)]
+    pub features: Option<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub groups: Option<String>,
+    #[serde(default)]
+    pub licenses: Option<String>,
+    #[serde(default)]
+    pub max_start_delay: Option<i64>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub node_count: Option<i64>,
+    #[serde(default)]
+    pub node_list: Option<String>,
+    #[serde(default)]
+    pub partition: Option<String>,
+    #[serde(default)]
+    pub purge_completed: Option<ReservationPurgeCompleted>,
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub tres: Option<String>,
+    #[serde(default)]
+    pub users: Option<String>,
+    #[serde(default)]
+    pub watts: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_reservation_purge_completed>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ReservationPurgeCompleted {
+    #[serde(default)]
+    pub time: Option<i64>,
+}
+
+/// SEE: <https://slurm.schedmd.com/rest_api.html#v0_0_38_reservations_response>
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ReservationsResponse {
+    #[serde(default)]
+    pub errors: Vec<Error>,
+    #[serde(default)]
+    pub meta: Meta,
+    #[serde(default)]
+    pub reservation: Vec<Reservation>,
+}
@@ -0,0 +1,632 @@
+//! Translation between the JSON of each API version and the normalized
+//! types in this crate, which follow the `v0.0.38` layout.
+//!
+//! Starting with `v0.0.39` slurmrestd wraps most numbers in
+//! `{"set": bool, "infinite": bool, "number": n}`, returns node and job
+//! states as arrays of flags, and nests partition settings into groups.
+//! Rather than keep a copy of every type per version, responses are
+//! rewritten into the `v0.0.38` shape before they are deserialized.
+use crate::{ApiVersion, INFINITE, INFINITE16, INFINITE64};
+use serde_json::{Map, Value};
+
+// Rewrite a response body from `version` of `plugin` into the shape our
+// types expect.
+pub(crate) fn response(version: ApiVersion, plugin: &str, body: &mut Value) {
+    if version < ApiVersion::V0_0_39 {
+        return;
+    }
+
+    unwrap_numbers(body, "");
+
+    let Some(body) = body.as_object_mut() else {
+        return;
+    };
+    if let Some(meta) = body.get_mut("meta") {
+        meta_v39(meta);
+    }
+    match plugin {
+        "slurm" => {
+            for node in objects(body.get_mut("nodes")) {
+                node_v39(node);
+            }
+            for job in objects(body.get_mut("jobs")) {
+                job_v39(job);
+            }
+            for partition in objects(body.get_mut("partitions")) {
+                partition_v39(partition);
+            }
+        }
+        "slurmdb" => {
+            for job in objects(body.get_mut("jobs")) {
+                db_job_v39(job);
+            }
+        }
+        _ => (),
+    }
+}
+
+// Rewrite a request body into the shape `version` expects.
+pub(crate) fn request(version: ApiVersion, body: &mut Value) {
+    let Some(body) = body.as_object_mut() else {
+        return;
+    };
+    for (key, value) in body.iter_mut() {
+        let jobs: Vec<&mut Map<String, Value>> = match (key.as_str(), value) {
+            ("job", Value::Object(job)) => vec![job],
+            ("jobs", jobs) => objects(Some(jobs)).collect(),
+            _ => continue,
+        };
+        for job in jobs {
+            if version < ApiVersion::V0_0_39 {
+                job_request_v38(job);
+            } else {
+                job_request_v39(job);
+            }
+        }
+    }
+}
+
+// Width of the field `key`, as the value Slurm uses for "no limit" in it.
+fn infinite(key: &str) -> i64 {
+    match key {
+        "cores_per_socket"
+        | "core_spec"
+        | "cpus_per_task"
+        | "job_factor"
+        | "over_time_limit"
+        | "priority_job_factor"
+        | "priority_tier"
+        | "sockets_per_node"
+        | "tasks_per_board"
+        | "tasks_per_core"
+        | "tasks_per_node"
+        | "tasks_per_socket"
+        | "thread_spec"
+        | "threads_per_core"
+        | "tier" => INFINITE16,
+        "accrue_time"
+        | "alloc_memory"
+        | "begin_time"
+        | "boot_time"
+        | "deadline"
+        | "default_memory_per_cpu"
+        | "default_memory_per_node"
+        | "eligible_time"
+        | "end_time"
+        | "free_mem"
+        | "last_backfill"
+        | "last_busy"
+        | "last_update"
+        | "maximum_memory_per_cpu"
+        | "maximum_memory_per_node"
+        | "memory_per_cpu"
+        | "memory_per_gpu"
+        | "memory_per_node"
+        | "partition_memory_per_cpu"
+        | "partition_memory_per_node"
+        | "pre_sus_time"
+        | "preempt_time"
+        | "real_memory"
+        | "resize_time"
+        | "slurmd_start_time"
+        | "start_time"
+        | "submit_time"
+        | "suspend_time" => INFINITE64,
+        _ => INFINITE,
+    }
+}
+
+// `v0.0.38` and earlier take Slurm's own 64 bit "no limit", which does not
+// fit in an `i64`.
+fn job_request_v38(job: &mut Map<String, Value>) {
+    for (key, value) in job.iter_mut() {
+        if infinite(key) == INFINITE64 && value.as_i64() == Some(INFINITE64) {
+            *value = Value::from(u64::MAX);
+        }
+    }
+}
+
+fn job_request_v39(job: &mut Map<String, Value>) {
+    // "No limit" is sent as a flag rather than a number.
+    for (key, value) in job.iter_mut() {
+        if value.as_i64() == Some(infinite(key)) {
+            *value = serde_json::json!({"set": true, "infinite": true, "number": 0});
+        }
+    }
+    // Node counts are no longer a `[min, max]` pair.
+    if let Some(Value::Array(nodes)) = job.remove("nodes") {
+        if let [min, max] = &nodes[..] {
+            job.insert("minimum_nodes".into(), min.clone());
+            job.insert("maximum_nodes".into(), max.clone());
+        }
+    }
+    // Job environments became a list of `NAME=value` strings.
+    if let Some(Value::Object(environment)) = job.get("environment") {
+        let environment = environment
+            .iter()
+            .map(|(k, v)| Value::String(format!("{k}={}", v.as_str().unwrap_or_default())))
+            .collect();
+        job.insert("environment".into(), Value::Array(environment));
+    }
+}
+
+// Every object in an optional array.
+fn objects(value: Option<&mut Value>) -> impl Iterator<Item = &mut Map<String, Value>> {
+    value
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+}
+
+// Replace `{"set", "infinite", "number"}` objects anywhere in `value`
+// with the number they hold, the `INFINITE` of the field's width, or
+// null when unset. `key` is the field `value` was found in.
+fn unwrap_numbers(value: &mut Value, key: &str) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(|v| unwrap_numbers(v, key)),
+        Value::Object(map) => {
+            let wrapped = map.contains_key("set")
+                && map.contains_key("number")
+                && map
+                    .keys()
+                    .all(|k| k == "set" || k == "infinite" || k == "number");
+            if !wrapped {
+                map.iter_mut().for_each(|(k, v)| unwrap_numbers(v, k));
+                return;
+            }
+
+            let flag = |key: &str| map.get(key).and_then(Value::as_bool).unwrap_or(false);
+            *value = if flag("infinite") {
+                Value::from(infinite(key))
+            } else if flag("set") {
+                map.remove("number").unwrap_or(Value::Null)
+            } else {
+                Value::Null
+            };
+        }
+        _ => (),
+    }
+}
+
+// Turn an array of flags into its first entry, returning the rest.
+fn split_flags(value: Option<&mut Value>) -> Vec<Value> {
+    match value {
+        Some(value @ Value::Array(_)) => {
+            let Value::Array(mut flags) = value.take() else {
+                unreachable!();
+            };
+            *value = if flags.is_empty() {
+                Value::Null
+            } else {
+                flags.remove(0)
+            };
+            flags
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Turn a number into a string and a list into a comma separated string,
+// or null if it is empty.
+fn stringify(value: Option<&mut Value>) {
+    let Some(value) = value else {
+        return;
+    };
+    match value {
+        Value::Number(n) => *value = Value::String(n.to_string()),
+        Value::Array(items) if items.is_empty() => *value = Value::Null,
+        Value::Array(_) => join_list(Some(value)),
+        _ => (),
+    }
+}
+
+// Turn an array of strings into a comma separated string.
+fn join_list(value: Option<&mut Value>) {
+    if let Some(value) = value {
+        if let Value::Array(items) = value {
+            let items: Vec<&str> = items.iter().filter_map(Value::as_str).collect();
+            *value = Value::String(items.join(","));
+        }
+    }
+}
+
+// Move `from` (a `/` separated path) to the top level key `to`.
+fn hoist(map: &mut Map<String, Value>, from: &str, to: &str) {
+    let mut keys = from.split('/');
+    let Some(first) = keys.next() else {
+        return;
+    };
+    let mut current = map.get_mut(first);
+    for key in keys {
+        current = current.and_then(|v| v.get_mut(key));
+    }
+    if let Some(value) = current.map(Value::take) {
+        if !value.is_null() {
+            map.insert(to.into(), value);
+        }
+    }
+}
+
+fn meta_v39(meta: &mut Value) {
+    let Some(meta) = meta.as_object_mut() else {
+        return;
+    };
+    if let Some(slurm) = meta.remove("slurm") {
+        meta.insert("Slurm".into(), slurm);
+    }
+    // `v0.0.40` started sending the version numbers as strings.
+    if let Some(Value::Object(version)) = meta.get_mut("Slurm").and_then(|s| s.get_mut("version")) {
+        for number in version.values_mut() {
+            if let Some(n) = number.as_str().and_then(|s| s.parse::<i64>().ok()) {
+                *number = Value::from(n);
+            }
+        }
+    }
+}
+
+fn node_v39(node: &mut Map<String, Value>) {
+    let flags = split_flags(node.get_mut("state"));
+    if !flags.is_empty() {
+        let state_flags = node
+            .entry("state_flags")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(state_flags) = state_flags {
+            state_flags.extend(flags);
+        }
+    }
+    let next_flags = split_flags(node.get_mut("next_state_after_reboot"));
+    if !next_flags.is_empty() {
+        node.insert(
+            "next_state_after_reboot_flags".into(),
+            Value::Array(next_flags),
+        );
+    }
+    hoist(node, "free_mem", "free_memory");
+    join_list(node.get_mut("features"));
+    join_list(node.get_mut("active_features"));
+}
+
+fn job_v39(job: &mut Map<String, Value>) {
+    let flags = split_flags(job.get_mut("job_state"));
+    if !flags.is_empty() {
+        job.insert("job_state_flags".into(), Value::Array(flags));
+    }
+    for key in ["exit_code", "derived_exit_code"] {
+        if job.get(key).is_some_and(Value::is_object) {
+            hoist(job, &format!("{key}/return_code"), key);
+        }
+    }
+    // Strings in `v0.0.38` that became numbers, or for `shared` a list.
+    for key in [
+        "core_spec",
+        "cores_per_socket",
+        "cpu_frequency_maximum",
+        "cpu_frequency_minimum",
+        "cpus_per_task",
+        "shared",
+        "thread_spec",
+    ] {
+        stringify(job.get_mut(key));
+    }
+    if let Some(Value::Object(resources)) = job.get_mut("job_resources") {
+        if resources.get("nodes").is_some_and(Value::is_object) {
+            hoist(resources, "nodes/count", "allocated_hosts");
+            hoist(resources, "cpus", "allocated_cpus");
+            hoist(resources, "nodes/list", "nodes");
+            resources.remove("allocated_nodes");
+        }
+        // `v0.0.39` made the allocation per node a list.
+        if resources
+            .get("allocated_nodes")
+            .is_some_and(Value::is_array)
+        {
+            resources.remove("allocated_nodes");
+        }
+    }
+}
+
+fn partition_v39(partition: &mut Map<String, Value>) {
+    // Only the nested layout has `nodes` as an object.
+    if !partition.get("nodes").is_some_and(Value::is_object) {
+        return;
+    }
+
+    for (from, to) in [
+        ("nodes/allowed_allocation", "allowed_allocation_nodes"),
+        ("nodes/total", "total_nodes"),
+        ("nodes/configured", "nodes"),
+        ("accounts/allowed", "allowed_accounts"),
+        ("accounts/deny", "denied_accounts"),
+        ("groups/allowed", "allowed_groups"),
+        ("qos/allowed", "allowed_qos"),
+        ("qos/deny", "denied_qos"),
+        ("qos/assigned", "qos"),
+        ("alternate", "alternative"),
+        ("tres/billing_weights", "billing_weights"),
+        ("tres/configured", "tres"),
+        ("cpus/total", "total_cpus"),
+        ("defaults/memory_per_cpu", "default_memory_per_cpu"),
+        (
+            "defaults/partition_memory_per_node",
+            "default_memory_per_node",
+        ),
+        ("defaults/time", "default_time_limit"),
+        ("grace_time", "preemption_grace_time"),
+        ("maximums/cpus_per_node", "maximum_cpus_per_node"),
+        (
+            "maximums/partition_memory_per_cpu",
+            "maximum_memory_per_cpu",
+        ),
+        (
+            "maximums/partition_memory_per_node",
+            "maximum_memory_per_node",
+        ),
+        ("maximums/nodes", "maximum_nodes_per_job"),
+        ("maximums/time", "max_time_limit"),
+        ("maximums/over_time_limit", "over_time_limit"),
+        ("minimums/nodes", "min_nodes_per_job"),
+        ("priority/job_factor", "priority_job_factor"),
+        ("priority/tier", "priority_tier"),
+        ("partition/state", "state"),
+    ] {
+        hoist(partition, from, to);
+    }
+    split_flags(partition.get_mut("state"));
+
+    // Whatever is left of the groups can not be represented.
+    for key in [
+        "accounts",
+        "groups",
+        "cpus",
+        "defaults",
+        "maximums",
+        "minimums",
+        "priority",
+        "partition",
+        "timeouts",
+    ] {
+        partition.remove(key);
+    }
+    if partition.get("qos").is_some_and(Value::is_object) {
+        partition.remove("qos");
+    }
+    if partition.get("tres").is_some_and(Value::is_object) {
+        partition.remove("tres");
+    }
+}
+
+fn db_job_v39(job: &mut Map<String, Value>) {
+    if let Some(Value::Object(state)) = job.get_mut("state") {
+        split_flags(state.get_mut("current"));
+    }
+    db_exit_codes_v39(job);
+    for step in objects(job.get_mut("steps")) {
+        split_flags(step.get_mut("state"));
+        db_exit_codes_v39(step);
+    }
+}
+
+// `v0.0.40` made the exit status a list of flags and renamed the signal
+// number to `id`.
+fn db_exit_codes_v39(map: &mut Map<String, Value>) {
+    for key in ["exit_code", "derived_exit_code"] {
+        let Some(Value::Object(exit_code)) = map.get_mut(key) else {
+            continue;
+        };
+        split_flags(exit_code.get_mut("status"));
+        if let Some(Value::Object(signal)) = exit_code.get_mut("signal") {
+            if let Some(id) = signal.remove("id") {
+                signal.entry("signal_id").or_insert(id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DbJobsResponse, JobProperties, JobSubmission, JobsResponse, NodesResponse,
+        PartitionsResponse,
+    };
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    // A response from each supported version, by fixture name.
+    macro_rules! fixtures {
+        ($name:literal) => {
+            [
+                (ApiVersion::V0_0_37, fixture!($name, "v0.0.37")),
+                (ApiVersion::V0_0_38, fixture!($name, "v0.0.38")),
+                (ApiVersion::V0_0_39, fixture!($name, "v0.0.39")),
+                (ApiVersion::V0_0_40, fixture!($name, "v0.0.40")),
+                (ApiVersion::V0_0_41, fixture!($name, "v0.0.41")),
+            ]
+        };
+    }
+    macro_rules! fixture {
+        ($name:literal, $version:literal) => {
+            include_str!(concat!("../tests/fixtures/", $name, "-", $version, ".json"))
+        };
+    }
+
+    const JOBS: [(ApiVersion, &str); 5] = fixtures!("jobs");
+    const NODES: [(ApiVersion, &str); 5] = fixtures!("nodes");
+    const PARTITIONS: [(ApiVersion, &str); 5] = fixtures!("partitions");
+    const DB_JOBS: [(ApiVersion, &str); 5] = fixtures!("db-jobs");
+
+    fn parse<T: DeserializeOwned>(version: ApiVersion, plugin: &str, fixture: &str) -> T {
+        let mut body: Value = serde_json::from_str(fixture).unwrap();
+        response(version, plugin, &mut body);
+        serde_path_to_error::deserialize(body)
+            .unwrap_or_else(|e| panic!("{version}: {}: {}", e.path(), e.inner()))
+    }
+
+    #[test]
+    fn jobs_response_deserializes_for_each_version() {
+        for (version, fixture) in JOBS {
+            let jobs: JobsResponse = parse(version, "slurm", fixture);
+
+            let job = &jobs.jobs[0];
+            assert_eq!(job.job_id, Some(1234), "{version}");
+            assert_eq!(job.job_state.as_deref(), Some("RUNNING"), "{version}");
+            assert_eq!(job.cpus_per_task.as_deref(), Some("4"), "{version}");
+            assert_eq!(job.cores_per_socket, None, "{version}");
+            assert_eq!(job.exit_code, Some(0), "{version}");
+            assert_eq!(
+                job.node_hostlist().unwrap().len(),
+                2,
+                "{version}: {:?}",
+                job.nodes
+            );
+            let resources = job.job_resources.as_ref().unwrap();
+            assert_eq!(
+                resources.nodes.as_deref(),
+                Some("gpu[001-002]"),
+                "{version}"
+            );
+            assert_eq!(resources.allocated_hosts, Some(2), "{version}");
+        }
+    }
+
+    #[test]
+    fn nodes_response_deserializes_for_each_version() {
+        for (version, fixture) in NODES {
+            let nodes: NodesResponse = parse(version, "slurm", fixture);
+            let node = &nodes.nodes[0];
+            assert_eq!(node.name.as_deref(), Some("gpu001"), "{version}");
+            assert!(
+                node.state
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case("mixed")),
+                "{version}: {:?}",
+                node.state
+            );
+            assert_eq!(node.state_flags, ["DRAIN"], "{version}");
+            assert_eq!(node.features.as_deref(), Some("a100,nvlink"), "{version}");
+            assert_eq!(node.free_memory, Some(400000), "{version}");
+            assert_eq!(node.boot_time, Some(1699990000), "{version}");
+            assert_eq!(node.cpus, Some(64), "{version}");
+        }
+    }
+
+    #[test]
+    fn partitions_response_deserializes_for_each_version() {
+        for (version, fixture) in PARTITIONS {
+            let partitions: PartitionsResponse = parse(version, "slurm", fixture);
+            let partition = &partitions.partitions[0];
+            assert_eq!(
+                partition.nodes.as_deref(),
+                Some("gpu[001-004]"),
+                "{version}"
+            );
+            assert_eq!(partition.total_nodes, Some(4), "{version}");
+            assert_eq!(partition.total_cpus, Some(256), "{version}");
+            assert_eq!(partition.state.as_deref(), Some("UP"), "{version}");
+            assert_eq!(partition.qos.as_deref(), Some("gpu"), "{version}");
+            assert_eq!(
+                partition.billing_weights.as_deref(),
+                Some("CPU=1.0,Mem=0.25G"),
+                "{version}"
+            );
+            assert_eq!(partition.default_memory_per_cpu, Some(4000), "{version}");
+            assert_eq!(partition.default_time_limit, Some(60), "{version}");
+            assert_eq!(partition.max_time_limit, Some(INFINITE), "{version}");
+            assert_eq!(partition.maximum_nodes_per_job, Some(INFINITE), "{version}");
+            assert_eq!(partition.min_nodes_per_job, Some(1), "{version}");
+            assert_eq!(partition.priority_tier, Some(1), "{version}");
+        }
+    }
+
+    #[test]
+    fn unlimited_partition_memory_is_64_bit() {
+        let partitions: PartitionsResponse = parse(ApiVersion::V0_0_40, "slurm", PARTITIONS[3].1);
+        let partition = &partitions.partitions[0];
+        assert_eq!(partition.maximum_memory_per_node, Some(INFINITE64));
+        assert_eq!(partition.default_memory_per_node, None);
+    }
+
+    #[test]
+    fn db_jobs_response_deserializes_for_each_version() {
+        for (version, fixture) in DB_JOBS {
+            let jobs: DbJobsResponse = parse(version, "slurmdb", fixture);
+            let job = &jobs.jobs[0];
+            assert_eq!(job.job_id, Some(1234), "{version}");
+            let state = job.state.as_ref().unwrap();
+            assert_eq!(state.current.as_deref(), Some("FAILED"), "{version}");
+            let exit_code = job.exit_code.as_ref().unwrap();
+            assert_eq!(exit_code.status.as_deref(), Some("SIGNALED"), "{version}");
+            assert_eq!(exit_code.return_code, Some(0), "{version}");
+            let signal = exit_code.signal.as_ref().unwrap();
+            assert_eq!(signal.signal_id, Some(9), "{version}");
+            assert_eq!(signal.name.as_deref(), Some("KILL"), "{version}");
+            let derived = job.derived_exit_code.as_ref().unwrap();
+            assert_eq!(derived.status.as_deref(), Some("SUCCESS"), "{version}");
+            assert_eq!(job.time.as_ref().unwrap().limit, Some(60), "{version}");
+
+            let step = &job.steps[0];
+            assert_eq!(step.state.as_deref(), Some("FAILED"), "{version}");
+            let exit_code = step.exit_code.as_ref().unwrap();
+            assert_eq!(exit_code.status.as_deref(), Some("SIGNALED"), "{version}");
+            let signal = exit_code.signal.as_ref().unwrap();
+            assert_eq!(signal.signal_id, Some(9), "{version}");
+        }
+    }
+
+    #[test]
+    fn shared_becomes_a_string() {
+        let jobs: JobsResponse = parse(ApiVersion::V0_0_40, "slurm", JOBS[3].1);
+        assert_eq!(jobs.jobs[0].shared.as_deref(), Some("user"));
+        assert_eq!(jobs.last_update, Some(1700000100));
+    }
+
+    fn submission(version: ApiVersion) -> Value {
+        let job = JobProperties::new()
+            .nodes(1, 4)
+            .time_limit(INFINITE)
+            .memory_per_node(INFINITE64)
+            .cpus_per_task(4)
+            .environment([("PATH", "/bin")]);
+        let mut body = serde_json::to_value(JobSubmission::new("#!/bin/sh", job)).unwrap();
+        request(version, &mut body);
+        body["job"].take()
+    }
+
+    #[test]
+    fn requests_before_v0_0_39_keep_their_shape() {
+        let job = submission(ApiVersion::V0_0_38);
+        assert_eq!(job["nodes"], json!([1, 4]));
+        assert_eq!(job["time_limit"], json!(INFINITE));
+        assert_eq!(job["memory_per_node"], json!(u64::MAX));
+        assert_eq!(job["environment"], json!({"PATH": "/bin"}));
+    }
+
+    #[test]
+    fn requests_from_v0_0_39() {
+        let infinite = json!({"set": true, "infinite": true, "number": 0});
+        let job = submission(ApiVersion::V0_0_40);
+        assert_eq!(job.get("nodes"), None);
+        assert_eq!(job["minimum_nodes"], json!(1));
+        assert_eq!(job["maximum_nodes"], json!(4));
+        assert_eq!(job["time_limit"], infinite);
+        assert_eq!(job["memory_per_node"], infinite);
+        assert_eq!(job["cpus_per_task"], json!(4));
+        assert_eq!(job["environment"], json!(["PATH=/bin"]));
+    }
+
+    #[test]
+    fn infinite_follows_the_field_width() {
+        let infinite = json!({"set": true, "infinite": true, "number": 0});
+        let mut body = json!({"jobs": [{
+            "time_limit": infinite,
+            "memory_per_node": infinite,
+            "cores_per_socket": infinite,
+        }]});
+        response(ApiVersion::V0_0_40, "slurm", &mut body);
+        let job = &body["jobs"][0];
+        assert_eq!(job["time_limit"], json!(INFINITE));
+        assert_eq!(job["memory_per_node"], json!(INFINITE64));
+        assert_eq!(job["cores_per_socket"], json!(INFINITE16.to_string()));
+    }
+}
@@ -0,0 +1,282 @@
+//! Retrying requests that failed for reasons that may go away.
+use crate::SlurmError;
+use reqwest::{Method, StatusCode};
+use std::{io::ErrorKind, time::Duration};
+
+// Slurm errors that mean slurmctld or slurmdbd could not be reached or
+// did not answer in time, rather than that the request was wrong.
+const TRANSIENT_ERRNOS: [i32; 10] = [
+    1001, // SLURM_COMMUNICATIONS_CONNECTION_ERROR
+    1002, // SLURM_COMMUNICATIONS_SEND_ERROR
+    1003, // SLURM_COMMUNICATIONS_RECEIVE_ERROR
+    1004, // SLURM_COMMUNICATIONS_SHUTDOWN_ERROR
+    1800, // SLURMCTLD_COMMUNICATIONS_CONNECTION_ERROR
+    1801, // SLURMCTLD_COMMUNICATIONS_SEND_ERROR
+    1802, // SLURMCTLD_COMMUNICATIONS_RECEIVE_ERROR
+    1803, // SLURMCTLD_COMMUNICATIONS_SHUTDOWN_ERROR
+    1804, // SLURMCTLD_COMMUNICATIONS_BACKOFF
+    5004, // SLURM_PROTOCOL_SOCKET_IMPL_TIMEOUT
+];
+
+/// When and how often failed requests are sent again. The default never
+/// retries; `RetryPolicy::new()` is a reasonable starting point.
+///
+/// A request is retried when it timed out, could not connect, came back
+/// with one of the retryable status codes, or Slurm reported only errors
+/// about reaching slurmctld or slurmdbd. Requests that may already have
+/// changed something, like `submit_job`, are only retried if they never
+/// reached slurmrestd, unless `retry_non_idempotent(true)` is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    statuses: Vec<StatusCode>,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::none()
+    }
+}
+
+impl RetryPolicy {
+    /// Up to 3 attempts, backing off from 200ms, with jitter, retrying
+    /// 429, 502, 503 and 504.
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// Never retry.
+    pub fn none() -> Self {
+        RetryPolicy::new().max_attempts(1)
+    }
+
+    /// How many times a request is sent at most, the first one included.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// How long to wait before the first retry. Each further retry waits
+    /// twice as long as the one before, up to `max_backoff`.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// The longest to wait between two attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Wait a random time between half and all of the backoff, so clients
+    /// that failed together do not all retry at the same moment. On by
+    /// default.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Status codes worth retrying, replacing the defaults. slurmrestd
+    /// also answers 500 for requests that will never succeed, so adding
+    /// it mostly delays those errors.
+    pub fn retry_statuses<I: IntoIterator<Item = StatusCode>>(mut self, statuses: I) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Also retry `POST` requests, like `submit_job`, after they may have
+    /// reached slurmrestd. This can submit a job twice.
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    // Whether a request that failed with `error` on attempt number
+    // `attempt` should be sent again.
+    pub(crate) fn retries(&self, method: &Method, error: &SlurmError, attempt: u32) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+        let (transient, sent) = match error {
+            SlurmError::Transport(e) => (e.is_timeout() || e.is_connect(), !e.is_connect()),
+            SlurmError::Socket(e) => match e.kind() {
+                ErrorKind::NotFound | ErrorKind::ConnectionRefused => (true, false),
+                ErrorKind::TimedOut | ErrorKind::ConnectionReset | ErrorKind::UnexpectedEof => {
+                    (true, true)
+                }
+                _ => (false, true),
+            },
+            SlurmError::Http { status, errors, .. } => {
+                let transient = self.statuses.contains(status)
+                    || (!errors.is_empty()
+                        && errors
+                            .iter()
+                            .all(|e| TRANSIENT_ERRNOS.contains(&e.error_number)));
+                (transient, true)
+            }
+            _ => (false, true),
+        };
+        let idempotent = !matches!(*method, Method::POST | Method::PATCH);
+        transient && (!sent || idempotent || self.retry_non_idempotent)
+    }
+
+    // How long to wait after attempt number `attempt` failed.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        if self.jitter {
+            backoff / 2 + backoff.mul_f64(fastrand::f64() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use std::io;
+
+    fn http(status: StatusCode, errnos: &[i32]) -> SlurmError {
+        SlurmError::Http {
+            status,
+            errors: errnos
+                .iter()
+                .map(|&error_number| Error {
+                    error: String::new(),
+                    error_number,
+                })
+                .collect(),
+            body: String::new(),
+        }
+    }
+
+    fn socket(kind: ErrorKind) -> SlurmError {
+        SlurmError::Socket(io::Error::from(kind))
+    }
+
+    #[test]
+    fn default_never_retries() {
+        let error = http(StatusCode::SERVICE_UNAVAILABLE, &[]);
+        assert!(!RetryPolicy::default().retries(&Method::GET, &error, 1));
+        assert!(RetryPolicy::new().retries(&Method::GET, &error, 1));
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        let policy = RetryPolicy::new().max_attempts(3);
+        let error = http(StatusCode::SERVICE_UNAVAILABLE, &[]);
+        assert!(policy.retries(&Method::GET, &error, 1));
+        assert!(policy.retries(&Method::GET, &error, 2));
+        assert!(!policy.retries(&Method::GET, &error, 3));
+        assert_eq!(RetryPolicy::new().max_attempts(0), RetryPolicy::none());
+    }
+
+    #[test]
+    fn status_codes() {
+        let policy = RetryPolicy::new();
+        for status in [429, 502, 503, 504] {
+            let error = http(StatusCode::from_u16(status).unwrap(), &[]);
+            assert!(policy.retries(&Method::GET, &error, 1), "{status}");
+        }
+        for status in [400, 401, 404, 500] {
+            let error = http(StatusCode::from_u16(status).unwrap(), &[]);
+            assert!(!policy.retries(&Method::GET, &error, 1), "{status}");
+        }
+
+        let policy = policy.retry_statuses([StatusCode::INTERNAL_SERVER_ERROR]);
+        let error = http(StatusCode::INTERNAL_SERVER_ERROR, &[]);
+        assert!(policy.retries(&Method::GET, &error, 1));
+        let error = http(StatusCode::SERVICE_UNAVAILABLE, &[]);
+        assert!(!policy.retries(&Method::GET, &error, 1));
+    }
+
+    #[test]
+    fn only_communication_errnos() {
+        let policy = RetryPolicy::new();
+        let error = http(StatusCode::INTERNAL_SERVER_ERROR, &[1800, 5004]);
+        assert!(policy.retries(&Method::GET, &error, 1));
+        // ESLURM_INVALID_JOB_ID alongside will not go away.
+        let error = http(StatusCode::INTERNAL_SERVER_ERROR, &[1800, 2017]);
+        assert!(!policy.retries(&Method::GET, &error, 1));
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let policy = RetryPolicy::new();
+        assert!(!policy.retries(&Method::GET, &SlurmError::Parse("x".into()), 1));
+        assert!(!policy.retries(&Method::GET, &socket(ErrorKind::PermissionDenied), 1));
+    }
+
+    #[test]
+    fn non_idempotent_requests() {
+        let policy = RetryPolicy::new();
+        let unsent = socket(ErrorKind::ConnectionRefused);
+        let sent = socket(ErrorKind::ConnectionReset);
+        let status = http(StatusCode::SERVICE_UNAVAILABLE, &[]);
+        for method in [Method::GET, Method::DELETE] {
+            assert!(policy.retries(&method, &unsent, 1), "{method}");
+            assert!(policy.retries(&method, &sent, 1), "{method}");
+            assert!(policy.retries(&method, &status, 1), "{method}");
+        }
+        for method in [Method::POST, Method::PATCH] {
+            assert!(policy.retries(&method, &unsent, 1), "{method}");
+            assert!(!policy.retries(&method, &sent, 1), "{method}");
+            assert!(!policy.retries(&method, &status, 1), "{method}");
+        }
+
+        let policy = policy.retry_non_idempotent(true);
+        assert!(policy.retries(&Method::POST, &sent, 1));
+        assert!(policy.retries(&Method::POST, &status, 1));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500))
+            .jitter(false);
+        let backoffs: Vec<u64> = (1..=5)
+            .map(|attempt| policy.backoff(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(backoffs, [100, 200, 400, 500, 500]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_and_all_of_the_backoff() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500));
+        for attempt in 1..=5 {
+            let full = Duration::from_millis(100 << (attempt - 1)).min(Duration::from_millis(500));
+            for _ in 0..100 {
+                let backoff = policy.backoff(attempt);
+                assert!(
+                    backoff >= full / 2 && backoff <= full,
+                    "{backoff:?} of {full:?}"
+                );
+            }
+        }
+    }
+}
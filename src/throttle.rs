@@ -0,0 +1,140 @@
+//! Keeping a client within an RPC budget agreed with the cluster admins.
+use std::{sync::Mutex, time::Duration};
+use tokio::{
+    sync::{Semaphore, SemaphorePermit},
+    time::Instant,
+};
+
+// Limits shared by every handle derived from one `SlurmClient`. Each
+// attempt at a request counts, retries included.
+#[derive(Debug, Default)]
+pub(crate) struct Throttle {
+    bucket: Option<Mutex<Bucket>>,
+    in_flight: Option<Semaphore>,
+}
+
+// A token bucket holding up to `burst` requests, refilled at `rate` per
+// second. Callers take a token even when it is empty and wait for the
+// debt to be refilled, so waiting requests are let through in order.
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl Throttle {
+    pub(crate) fn new(rate: Option<(f64, u32)>, max_in_flight: Option<usize>) -> Self {
+        Throttle {
+            bucket: rate.map(|(rate, burst)| {
+                let burst = f64::from(burst.max(1));
+                Mutex::new(Bucket {
+                    rate,
+                    burst,
+                    tokens: burst,
+                    refilled: Instant::now(),
+                })
+            }),
+            in_flight: max_in_flight.map(Semaphore::new),
+        }
+    }
+
+    // Wait until a request may be sent. The returned permit holds a slot
+    // of the in-flight limit until it is dropped.
+    pub(crate) async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        if let Some(bucket) = &self.bucket {
+            let wait = bucket.lock().unwrap_or_else(|e| e.into_inner()).take();
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        match &self.in_flight {
+            // The semaphore is never closed.
+            Some(in_flight) => in_flight.acquire().await.ok(),
+            None => None,
+        }
+    }
+}
+
+impl Bucket {
+    // Take a token and return how long to wait before using it.
+    fn take(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            // A rate small enough to overflow means never.
+            Duration::try_from_secs_f64(-self.tokens / self.rate).unwrap_or(Duration::MAX)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn bursts_then_waits_for_the_rate() {
+        let throttle = Throttle::new(Some((2.0, 3)), None);
+        let start = Instant::now();
+        for _ in 0..3 {
+            throttle.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        throttle.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+        throttle.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refills_up_to_the_burst() {
+        let throttle = Throttle::new(Some((1.0, 2)), None);
+        throttle.acquire().await;
+        throttle.acquire().await;
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        let start = Instant::now();
+        throttle.acquire().await;
+        throttle.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        throttle.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tiny_rates_do_not_overflow() {
+        let throttle = Throttle::new(Some((1e-20, 1)), None);
+        throttle.acquire().await;
+        let bucket = throttle.bucket.as_ref().unwrap();
+        assert_eq!(bucket.lock().unwrap().take(), Duration::MAX);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_requests_in_flight() {
+        let throttle = Throttle::new(None, Some(2));
+        let first = throttle.acquire().await;
+        let second = throttle.acquire().await;
+        assert!(first.is_some() && second.is_some());
+
+        let wait = Duration::from_secs(1);
+        assert!(tokio::time::timeout(wait, throttle.acquire())
+            .await
+            .is_err());
+        drop(first);
+        assert!(tokio::time::timeout(wait, throttle.acquire()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn unlimited() {
+        let throttle = Throttle::default();
+        assert!(throttle.acquire().await.is_none());
+    }
+}
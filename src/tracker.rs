@@ -0,0 +1,260 @@
+//! Polling jobs, nodes and partitions without fetching unchanged data.
+use crate::{Conditional, JobsResponse, NodesResponse, PartitionsResponse, Result, Slurm};
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Mutex;
+
+/// Wraps a `Slurm` client to remember when jobs, nodes and partitions were
+/// last fetched, and only fetch them again if slurmctld reports a change
+/// since then. Meant for callers that poll, like dashboards.
+///
+/// Versions before `v0.0.40` do not report when the data last changed, so
+/// the local clock is used instead; it should be roughly in sync with the
+/// controller's.
+pub struct UpdateTracker {
+    slurm: Slurm,
+    jobs: Mutex<Option<Tracked<JobsResponse>>>,
+    nodes: Mutex<Option<Tracked<NodesResponse>>>,
+    partitions: Mutex<Option<Tracked<PartitionsResponse>>>,
+}
+
+// The last response for a resource and the `update_time` to send next.
+struct Tracked<T> {
+    update_time: i64,
+    response: Arc<T>,
+}
+
+impl UpdateTracker {
+    /// Track updates through `slurm`.
+    pub fn new(slurm: Slurm) -> Self {
+        UpdateTracker {
+            slurm,
+            jobs: Mutex::new(None),
+            nodes: Mutex::new(None),
+            partitions: Mutex::new(None),
+        }
+    }
+
+    /// The client requests are sent with.
+    pub fn slurm(&self) -> &Slurm {
+        &self.slurm
+    }
+
+    /// All jobs, fetched again only if any changed since the last call.
+    pub async fn jobs(&self) -> Result<Arc<JobsResponse>> {
+        refresh(
+            &self.jobs,
+            || self.slurm.get_jobs(),
+            |update_time| self.slurm.get_jobs_since(update_time),
+            |r| r.last_update,
+        )
+        .await
+    }
+
+    /// All nodes, fetched again only if any changed since the last call.
+    pub async fn nodes(&self) -> Result<Arc<NodesResponse>> {
+        refresh(
+            &self.nodes,
+            || self.slurm.get_nodes(),
+            |update_time| self.slurm.get_nodes_since(update_time),
+            |r| r.last_update,
+        )
+        .await
+    }
+
+    /// All partitions, fetched again only if any changed since the last
+    /// call.
+    pub async fn partitions(&self) -> Result<Arc<PartitionsResponse>> {
+        refresh(
+            &self.partitions,
+            || self.slurm.get_partitions(),
+            |update_time| self.slurm.get_partitions_since(update_time),
+            |r| r.last_update,
+        )
+        .await
+    }
+
+    /// Forget everything, so the next call of each method fetches all data.
+    pub async fn reset(&self) {
+        *self.jobs.lock().await = None;
+        *self.nodes.lock().await = None;
+        *self.partitions.lock().await = None;
+    }
+}
+
+// Fetch everything the first time and only changes after that. The lock
+// is held while fetching, so concurrent callers wait for one request
+// instead of each sending their own.
+async fn refresh<T, All, AllFut, Since, SinceFut>(
+    slot: &Mutex<Option<Tracked<T>>>,
+    all: All,
+    since: Since,
+    last_update: fn(&T) -> Option<i64>,
+) -> Result<Arc<T>>
+where
+    All: FnOnce() -> AllFut,
+    AllFut: Future<Output = Result<T>>,
+    Since: FnOnce(i64) -> SinceFut,
+    SinceFut: Future<Output = Result<Conditional<T>>>,
+{
+    let mut slot = slot.lock().await;
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+
+    let response = match slot.as_ref() {
+        None => all().await?,
+        Some(tracked) => match since(tracked.update_time).await? {
+            Conditional::Modified(response) => response,
+            Conditional::NotModified => return Ok(tracked.response.clone()),
+        },
+    };
+    let response = Arc::new(response);
+    *slot = Some(Tracked {
+        update_time: last_update(&response).unwrap_or(started),
+        response: response.clone(),
+    });
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiVersion;
+    use serde_json::{json, Value};
+    use std::sync::Mutex as StdMutex;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    // Answers each request with the next of `bodies` and records the
+    // request lines it got.
+    async fn serve(bodies: Vec<Value>) -> (String, Arc<StdMutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(StdMutex::new(Vec::new()));
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            for body in bodies {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request);
+                seen.lock()
+                    .unwrap()
+                    .push(request.lines().next().unwrap().to_string());
+
+                let body = body.to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (endpoint, requests)
+    }
+
+    fn slurm(endpoint: &str, version: ApiVersion) -> Slurm {
+        Slurm::builder()
+            .endpoint(endpoint)
+            .user("alice")
+            .token("abc")
+            .api_version(version)
+            .build()
+            .unwrap()
+    }
+
+    fn meta(version: ApiVersion) -> Value {
+        json!({"plugin": {"type": format!("openapi/{version}")}})
+    }
+
+    fn nodes(version: ApiVersion, last_update: Option<i64>, errnos: &[i32]) -> Value {
+        let errors: Vec<Value> = errnos
+            .iter()
+            .map(|n| json!({"error": "", "error_number": n}))
+            .collect();
+        json!({
+            "meta": meta(version),
+            "errors": errors,
+            "last_update": last_update,
+            "nodes": [{"name": "gpu001"}],
+        })
+    }
+
+    #[tokio::test]
+    async fn no_change_in_data() {
+        let v38 = ApiVersion::V0_0_38;
+        let (endpoint, requests) = serve(vec![nodes(v38, None, &[1900])]).await;
+        let changed = slurm(&endpoint, v38)
+            .get_nodes_since(1700000000)
+            .await
+            .unwrap();
+        assert!(changed.modified().is_none());
+        assert_eq!(
+            requests.lock().unwrap()[0],
+            "GET /slurm/v0.0.38/nodes?update_time=1700000000 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn last_update_not_after_update_time() {
+        let v40 = ApiVersion::V0_0_40;
+        let (endpoint, _) = serve(vec![
+            nodes(v40, Some(1700000000), &[]),
+            nodes(v40, Some(1700000001), &[]),
+        ])
+        .await;
+        let slurm = slurm(&endpoint, v40);
+        let unchanged = slurm.get_nodes_since(1700000000).await.unwrap();
+        assert!(unchanged.modified().is_none());
+        let changed = slurm.get_nodes_since(1700000000).await.unwrap();
+        let nodes = changed.modified().unwrap();
+        assert_eq!(nodes.nodes[0].name.as_deref(), Some("gpu001"));
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_still_reported() {
+        let v38 = ApiVersion::V0_0_38;
+        let (endpoint, _) = serve(vec![nodes(v38, None, &[1900, 2017])]).await;
+        let error = slurm(&endpoint, v38).get_nodes_since(0).await.unwrap_err();
+        assert_eq!(error.slurm_errors().len(), 1);
+        assert_eq!(error.slurm_errors()[0].error_number, 2017);
+    }
+
+    #[tokio::test]
+    async fn tracker_sends_the_last_update() {
+        let v38 = ApiVersion::V0_0_38;
+        let (endpoint, requests) = serve(vec![
+            nodes(v38, Some(1700000000), &[]),
+            nodes(v38, None, &[1900]),
+            nodes(v38, Some(1700000100), &[]),
+        ])
+        .await;
+        let tracker = UpdateTracker::new(slurm(&endpoint, v38));
+
+        let first = tracker.nodes().await.unwrap();
+        let unchanged = tracker.nodes().await.unwrap();
+        assert!(Arc::ptr_eq(&first, &unchanged));
+        let changed = tracker.nodes().await.unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+        assert_eq!(changed.last_update, Some(1700000100));
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "GET /slurm/v0.0.38/nodes HTTP/1.1",
+                "GET /slurm/v0.0.38/nodes?update_time=1700000000 HTTP/1.1",
+                "GET /slurm/v0.0.38/nodes?update_time=1700000000 HTTP/1.1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! The connection shared by every `Slurm` and `SlurmDB` handle.
+use crate::{
+    normalize, throttle::Throttle, ApiVersion, CredentialProvider, Credentials, Meta, Result,
+    RetryPolicy, SlurmError, Status,
+};
+use reqwest::{header, Client, Method, Request, Response, StatusCode, Url};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::SemaphorePermit;
+
+#[cfg(unix)]
+use crate::unix::UnixSocket;
+
+// Header names must be lowercase to be used with `HeaderName::from_static`.
+const SLURM_USER: &str = "x-slurm-user-name";
+const SLURM_TOKEN: &str = "x-slurm-user-token";
+
+// A response and the slot of the in-flight limit it takes up, which is
+// held until the body has been read.
+type Sent<'a> = (Response, Option<SemaphorePermit<'a>>);
+
+// One HTTP client and one source of credentials for a slurmrestd endpoint.
+pub(crate) struct Transport {
+    // `None` for `auth/local`, where slurmrestd trusts the socket's peer
+    // and no auth headers are sent.
+    pub(crate) credentials: Option<Arc<dyn CredentialProvider>>,
+    pub(crate) endpoint: Url,
+    pub(crate) client: Arc<Client>,
+    pub(crate) version: ApiVersion,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) throttle: Throttle,
+    // Set to send requests over a unix socket instead of `client`.
+    #[cfg(unix)]
+    pub(crate) socket: Option<UnixSocket>,
+}
+
+impl Transport {
+    // This will be our internal request builder. `plugin` is either
+    // `slurm` or `slurmdb`.
+    pub(crate) fn request<B>(
+        &self,
+        plugin: &str,
+        method: Method,
+        path: &str,
+        body: B,
+        query: Option<Vec<(&str, String)>>,
+    ) -> Result<Request>
+    where
+        B: Serialize,
+    {
+        // https://slurm-endpoint/{slurm,slurmdb}/v0.0.38/{nodes, diag, etc..}
+        let url_path = format!("{}/{}/{}", plugin, self.version, path);
+        let url = self.endpoint.join(&url_path)?;
+
+        // Start building up our request. The auth headers are added when
+        // it is sent, so they are never stale.
+        let mut request_builder = self.client.request(method.clone(), url).header(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+
+        // if we have query variable, add it to our Url
+        match query {
+            None => (),
+            Some(q) => {
+                request_builder = request_builder.query(&q);
+            }
+        }
+
+        // Add the body if our request method is something other than
+        // GET or DELETE
+        if method != Method::GET && method != Method::DELETE {
+            let mut body = serde_json::to_value(body).map_err(SlurmError::Serialize)?;
+            normalize::request(self.version, &mut body);
+            request_builder = request_builder.json(&body);
+        }
+
+        // Build it!
+        Ok(request_builder.build()?)
+    }
+
+    // Add the auth headers and send the request. If slurmrestd rejects the
+    // credentials and the provider has different ones, send it once more.
+    async fn send(&self, request: Request) -> Result<Sent<'_>> {
+        let Some(provider) = &self.credentials else {
+            return self.send_once(request, None).await;
+        };
+        let retry = request.try_clone();
+        let credentials = provider.credentials().await?;
+        let sent = self.send_once(request, Some(&credentials)).await?;
+        if sent.0.status() != StatusCode::UNAUTHORIZED {
+            return Ok(sent);
+        }
+        match retry {
+            Some(retry) if provider.invalidate(&credentials).await => {
+                // Free the slot first, or a limit of one would wait on itself.
+                drop(sent);
+                let credentials = provider.credentials().await?;
+                self.send_once(retry, Some(&credentials)).await
+            }
+            _ => Ok(sent),
+        }
+    }
+
+    async fn send_once(
+        &self,
+        mut request: Request,
+        credentials: Option<&Credentials>,
+    ) -> Result<Sent<'_>> {
+        if let Some(credentials) = credentials {
+            let headers = request.headers_mut();
+            headers.insert(
+                header::HeaderName::from_static(SLURM_USER),
+                header::HeaderValue::from_str(&credentials.user)?,
+            );
+            let mut token = header::HeaderValue::from_str(&credentials.token)?;
+            token.set_sensitive(true);
+            headers.insert(header::HeaderName::from_static(SLURM_TOKEN), token);
+        }
+        let permit = self.throttle.acquire().await;
+        #[cfg(unix)]
+        if let Some(socket) = &self.socket {
+            return Ok((socket.execute(request).await?, permit));
+        }
+        Ok((self.client.execute(request).await?, permit))
+    }
+
+    // Fetch a JSON document that lives outside the versioned plugin paths,
+    // such as `openapi/v3`. Returns `None` if slurmrestd does not serve it.
+    pub(crate) async fn document(&self, path: &str) -> Result<Option<serde_json::Value>> {
+        let url = self.endpoint.join(path)?;
+        let request = self.client.get(url).build()?;
+        let (response, _permit) = self.send(request).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let status = response.status();
+        let body = response.text().await?;
+        if status != StatusCode::OK {
+            return Err(SlurmError::Http {
+                status,
+                errors: Vec::new(),
+                body,
+            });
+        }
+        serde_json::from_str(&body)
+            .map(Some)
+            .map_err(|e| SlurmError::Deserialize {
+                path: ".".into(),
+                source: e,
+            })
+    }
+
+    // Send a request and parse the response from `plugin` into `T`,
+    // retrying as the retry policy allows.
+    pub(crate) async fn execute<T>(&self, plugin: &str, mut request: Request) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let method = request.method().clone();
+        let mut attempt = 1;
+        loop {
+            let next = request.try_clone();
+            let error = match self.send(request).await {
+                Ok((response, _permit)) => {
+                    match parse_response(self.version, plugin, response).await {
+                        Ok(parsed) => return Ok(parsed),
+                        Err(e) => e,
+                    }
+                }
+                Err(e) => e,
+            };
+            match next {
+                Some(next) if self.retry_policy.retries(&method, &error, attempt) => {
+                    tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+                    request = next;
+                    attempt += 1;
+                }
+                _ => return Err(error),
+            }
+        }
+    }
+}
+
+// Turn a response into `T`. Anything other than a 200 becomes
+// `SlurmError::Http`, carrying whatever Slurm errors the body held.
+async fn parse_response<T>(version: ApiVersion, plugin: &str, response: Response) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let status = response.status();
+    let body = response.text().await?;
+
+    if status != StatusCode::OK {
+        let errors = serde_json::from_str::<Status>(&body)
+            .map(|s| s.errors)
+            .unwrap_or_default();
+        return Err(SlurmError::Http {
+            status,
+            errors,
+            body,
+        });
+    }
+
+    let mut body: serde_json::Value =
+        serde_json::from_str(&body).map_err(|e| SlurmError::Deserialize {
+            path: ".".into(),
+            source: e,
+        })?;
+    check_version(version, &body)?;
+    normalize::response(version, plugin, &mut body);
+
+    serde_path_to_error::deserialize(body).map_err(|e| SlurmError::Deserialize {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+    })
+}
+
+// Make sure the response was produced by the API version it is about to be
+// parsed as.
+fn check_version(expected: ApiVersion, body: &serde_json::Value) -> Result<()> {
+    let actual = body
+        .get("meta")
+        .and_then(|meta| Meta::deserialize(meta).ok())
+        .and_then(|meta| meta.api_version());
+    match actual {
+        Some(actual) if actual != expected => Err(SlurmError::VersionMismatch { expected, actual }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn meta(plugin: serde_json::Value) -> serde_json::Value {
+        json!({ "meta": { "plugin": plugin } })
+    }
+
+    #[test]
+    fn matching_versions() {
+        let v38 = meta(json!({"type": "openapi/v0.0.38"}));
+        assert!(check_version(ApiVersion::V0_0_38, &v38).is_ok());
+        let db38 = meta(json!({"type": "openapi/dbv0.0.38"}));
+        assert!(check_version(ApiVersion::V0_0_38, &db38).is_ok());
+        let v40 = meta(json!({"type": "openapi/slurmctld", "data_parser": "data_parser/v0.0.40"}));
+        assert!(check_version(ApiVersion::V0_0_40, &v40).is_ok());
+    }
+
+    #[test]
+    fn mismatched_versions() {
+        let v41 = meta(json!({"type": "openapi/slurmdbd", "data_parser": "data_parser/v0.0.41"}));
+        let error = check_version(ApiVersion::V0_0_40, &v41).unwrap_err();
+        assert!(matches!(
+            error,
+            SlurmError::VersionMismatch {
+                expected: ApiVersion::V0_0_40,
+                actual: ApiVersion::V0_0_41,
+            }
+        ));
+    }
+
+    #[test]
+    fn responses_without_a_version_are_accepted() {
+        assert!(check_version(ApiVersion::V0_0_38, &json!({})).is_ok());
+        let unknown = meta(json!({"type": "openapi/slurmctld"}));
+        assert!(check_version(ApiVersion::V0_0_40, &unknown).is_ok());
+    }
+}
@@ -0,0 +1,420 @@
+//! Slurm TRES (trackable resources) strings, like
+//! `cpu=64,mem=256G,node=1,billing=64,gres/gpu:a100=4`.
+use crate::{JobResponseProperties, Node, Partition, Reservation, Result, SlurmError};
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+// TRES types counted in megabytes, which Slurm writes with a unit suffix.
+const SIZES: [&str; 4] = ["mem", "vmem", "bb", "fs"];
+
+/// The type and, for types like `gres` or `license`, the name of a TRES,
+/// written `type/name`, e.g. `cpu` or `gres/gpu:a100`. Types are case
+/// insensitive and kept in lower case.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TresKey {
+    kind: String,
+    name: Option<String>,
+}
+
+impl TresKey {
+    /// A TRES of type `kind`, like `cpu` or `gres`, with an optional name,
+    /// like `gpu:a100`.
+    pub fn new<K: ToString, N: ToString>(kind: K, name: Option<N>) -> Self {
+        TresKey {
+            kind: kind.to_string().to_lowercase(),
+            name: name.map(|n| n.to_string()),
+        }
+    }
+
+    /// The type, like `cpu`, `mem` or `gres`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// The name, like `gpu:a100` for `gres/gpu:a100`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Whether counts are sizes in megabytes, like `mem`.
+    pub fn is_size(&self) -> bool {
+        SIZES.contains(&self.kind.as_str())
+    }
+}
+
+impl fmt::Display for TresKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}/{name}", self.kind),
+            None => f.write_str(&self.kind),
+        }
+    }
+}
+
+impl FromStr for TresKey {
+    type Err = SlurmError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.is_empty() {
+            return Err(SlurmError::Parse("TRES type ''".to_string()));
+        }
+        Ok(match s.split_once('/') {
+            Some((kind, name)) => TresKey::new(kind, Some(name)),
+            None => TresKey::new(s, None::<&str>),
+        })
+    }
+}
+
+/// Counts of trackable resources, parsed from or written as a Slurm TRES
+/// string. Sizes, like `mem`, are counted in megabytes as slurmctld does;
+/// `256G` is parsed as 262144. Other resources are whole numbers without a
+/// unit. Resources keep the order they were written or added in.
+///
+/// Resources that are not listed count as zero, so adding, subtracting,
+/// comparing and `fits_within` work across strings that list different
+/// resources. Comparing ignores the order resources are listed in.
+#[derive(Debug, Default, Clone)]
+pub struct Tres {
+    counts: Vec<(TresKey, u64)>,
+}
+
+impl Tres {
+    /// No resources.
+    pub fn new() -> Self {
+        Tres::default()
+    }
+
+    /// The count of a resource, written like `cpu` or `gres/gpu:a100`.
+    /// Zero if it is not listed.
+    pub fn get(&self, key: &str) -> u64 {
+        key.parse()
+            .map_or(0, |key: TresKey| self.get_key(&key).unwrap_or(0))
+    }
+
+    /// The count of a resource, if it is listed.
+    pub fn get_key(&self, key: &TresKey) -> Option<u64> {
+        self.counts.iter().find(|(k, _)| k == key).map(|(_, n)| *n)
+    }
+
+    /// Set the count of a resource, written like `cpu` or `gres/gpu:a100`.
+    pub fn set(&mut self, key: &str, count: u64) -> Result<()> {
+        let key = key.parse()?;
+        self.set_key(key, count);
+        Ok(())
+    }
+
+    /// Set the count of a resource.
+    pub fn set_key(&mut self, key: TresKey, count: u64) {
+        match self.counts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, n)) => *n = count,
+            None => self.counts.push((key, count)),
+        }
+    }
+
+    /// Remove a resource, returning its count if it was listed.
+    pub fn remove(&mut self, key: &TresKey) -> Option<u64> {
+        let i = self.counts.iter().position(|(k, _)| k == key)?;
+        Some(self.counts.remove(i).1)
+    }
+
+    /// Each resource and its count, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&TresKey, u64)> {
+        self.counts.iter().map(|(k, n)| (k, *n))
+    }
+
+    /// The number of resources listed.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Whether no resources are listed.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// CPUs.
+    pub fn cpu(&self) -> u64 {
+        self.get("cpu")
+    }
+
+    /// Memory in megabytes.
+    pub fn mem(&self) -> u64 {
+        self.get("mem")
+    }
+
+    /// Nodes.
+    pub fn node(&self) -> u64 {
+        self.get("node")
+    }
+
+    /// Billing units.
+    pub fn billing(&self) -> u64 {
+        self.get("billing")
+    }
+
+    /// The count of a generic resource, like `gpu` or `gpu:a100`.
+    pub fn gres(&self, name: &str) -> u64 {
+        self.get_key(&TresKey::new("gres", Some(name))).unwrap_or(0)
+    }
+
+    /// Whether no resource has a higher count than in `limit`, e.g. whether
+    /// a job's request fits in what a node has left.
+    pub fn fits_within(&self, limit: &Tres) -> bool {
+        self.iter()
+            .all(|(key, n)| n <= limit.get_key(key).unwrap_or(0))
+    }
+}
+
+impl PartialEq for Tres {
+    fn eq(&self, other: &Tres) -> bool {
+        let same = |a: &Tres, b: &Tres| a.iter().all(|(key, n)| n == b.get_key(key).unwrap_or(0));
+        same(self, other) && same(other, self)
+    }
+}
+
+impl Eq for Tres {}
+
+impl FromStr for Tres {
+    type Err = SlurmError;
+
+    /// Parse a TRES string. An empty string has no resources.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |reason: &str| SlurmError::Parse(format!("TRES '{s}': {reason}"));
+
+        let mut tres = Tres::new();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, count) = pair.split_once('=').ok_or_else(|| error("missing '='"))?;
+            let key: TresKey = key.parse().map_err(|_| error("missing type"))?;
+            // Only sizes take a unit, and only their units allow fractions.
+            let count = if key.is_size() {
+                parse_size(count)
+            } else {
+                count.parse().ok()
+            };
+            let count = count.ok_or_else(|| error("invalid count"))?;
+            tres.set_key(key, count);
+        }
+        Ok(tres)
+    }
+}
+
+/// Writes the resources as a TRES string the way Slurm does, with sizes in
+/// the largest unit that keeps them exact, like `mem=256G`.
+impl fmt::Display for Tres {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, count)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{key}=")?;
+            if key.is_size() {
+                write_size(f, count)?;
+            } else {
+                write!(f, "{count}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AddAssign<&Tres> for Tres {
+    fn add_assign(&mut self, other: &Tres) {
+        for (key, n) in other.iter() {
+            let sum = self.get_key(key).unwrap_or(0).saturating_add(n);
+            self.set_key(key.clone(), sum);
+        }
+    }
+}
+
+impl Add<&Tres> for Tres {
+    type Output = Tres;
+
+    fn add(mut self, other: &Tres) -> Tres {
+        self += other;
+        self
+    }
+}
+
+/// Subtracts counts, stopping at zero rather than going negative.
+impl SubAssign<&Tres> for Tres {
+    fn sub_assign(&mut self, other: &Tres) {
+        for (key, n) in other.iter() {
+            if let Some(count) = self.get_key(key) {
+                self.set_key(key.clone(), count.saturating_sub(n));
+            }
+        }
+    }
+}
+
+impl Sub<&Tres> for Tres {
+    type Output = Tres;
+
+    fn sub(mut self, other: &Tres) -> Tres {
+        self -= other;
+        self
+    }
+}
+
+// A size in megabytes, which may have a unit suffix, like `4`, `256G` or
+// `1.5T`.
+fn parse_size(count: &str) -> Option<u64> {
+    let (number, scale) = match count.char_indices().last()? {
+        (i, 'K' | 'k') => (&count[..i], 1.0 / 1024.0),
+        (i, 'M' | 'm') => (&count[..i], 1.0),
+        (i, 'G' | 'g') => (&count[..i], 1024.0),
+        (i, 'T' | 't') => (&count[..i], 1024.0 * 1024.0),
+        (i, 'P' | 'p') => (&count[..i], 1024.0 * 1024.0 * 1024.0),
+        _ => (count, 1.0),
+    };
+    if let Ok(n) = number.parse::<u64>() {
+        if scale >= 1.0 {
+            return n.checked_mul(scale as u64);
+        }
+    }
+    let n = number.parse::<f64>().ok()?;
+    (n >= 0.0 && n.is_finite()).then(|| (n * scale).ceil() as u64)
+}
+
+fn write_size(f: &mut fmt::Formatter<'_>, megabytes: u64) -> fmt::Result {
+    let mut size = megabytes;
+    let mut units = ["M", "G", "T", "P"].into_iter().peekable();
+    let mut unit = units.next().expect("units is not empty");
+    while size != 0 && size.is_multiple_of(1024) && units.peek().is_some() {
+        size /= 1024;
+        unit = units.next().expect("a unit is left");
+    }
+    write!(f, "{size}{unit}")
+}
+
+impl Node {
+    /// The resources the node has.
+    pub fn total_tres(&self) -> Result<Tres> {
+        parse(&self.tres)
+    }
+
+    /// The resources allocated to jobs on the node.
+    pub fn used_tres(&self) -> Result<Tres> {
+        parse(&self.tres_used)
+    }
+}
+
+impl Partition {
+    /// The resources of all nodes in the partition.
+    pub fn total_tres(&self) -> Result<Tres> {
+        parse(&self.tres)
+    }
+}
+
+impl Reservation {
+    /// The resources held by the reservation.
+    pub fn reserved_tres(&self) -> Result<Tres> {
+        parse(&self.tres)
+    }
+}
+
+impl JobResponseProperties {
+    /// The resources the job asked for.
+    pub fn requested_tres(&self) -> Result<Tres> {
+        parse(&self.tres_req_str)
+    }
+
+    /// The resources allocated to the job.
+    pub fn allocated_tres(&self) -> Result<Tres> {
+        parse(&self.tres_alloc_str)
+    }
+}
+
+fn parse(field: &Option<String>) -> Result<Tres> {
+    field.as_deref().unwrap_or_default().parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tres(s: &str) -> Tres {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn keys() {
+        let key: TresKey = "gres/gpu:a100".parse().unwrap();
+        assert_eq!((key.kind(), key.name()), ("gres", Some("gpu:a100")));
+        assert_eq!(key.to_string(), "gres/gpu:a100");
+
+        // Types are case insensitive, names are not.
+        let key: TresKey = "License/MATLAB".parse().unwrap();
+        assert_eq!(key.to_string(), "license/MATLAB");
+        assert!("".parse::<TresKey>().is_err());
+    }
+
+    #[test]
+    fn sizes_are_counted_in_megabytes() {
+        assert_eq!(tres("mem=256G").mem(), 256 * 1024);
+        assert_eq!(tres("mem=1024").mem(), 1024);
+        assert_eq!(tres("mem=1.5G").mem(), 1536);
+        // Fractions of a megabyte round up.
+        assert_eq!(tres("mem=512K").mem(), 1);
+        assert_eq!(tres("fs/disk=2t").get("fs/disk"), 2 * 1024 * 1024);
+    }
+
+    #[test]
+    fn sizes_are_written_in_the_largest_exact_unit() {
+        assert_eq!(tres("mem=1024M").to_string(), "mem=1G");
+        assert_eq!(tres("mem=1536M").to_string(), "mem=1536M");
+        assert_eq!(tres("bb/datawarp=1048576").to_string(), "bb/datawarp=1T");
+        assert_eq!(tres("node=1024").to_string(), "node=1024");
+    }
+
+    #[test]
+    fn round_trips_a_node_string() {
+        let s = "cpu=64,mem=256G,node=1,billing=64,gres/gpu:a100=4";
+        let parsed = tres(s);
+        assert_eq!((parsed.cpu(), parsed.node(), parsed.billing()), (64, 1, 64));
+        assert_eq!(parsed.gres("gpu:a100"), 4);
+        assert_eq!(parsed.gres("gpu"), 0);
+        assert_eq!(parsed.to_string(), s);
+        assert!(tres("").is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_pairs() {
+        for s in ["cpu", "=4", "cpu=", "cpu=x", "cpu=-1", "mem=1Q", "mem=-1G"] {
+            assert!(s.parse::<Tres>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn only_sizes_take_units_and_fractions() {
+        for s in ["cpu=1.5", "cpu=4K", "node=2G", "gres/gpu=4G", "billing=0.5"] {
+            assert!(s.parse::<Tres>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn unlisted_resources_count_as_zero() {
+        let total = tres("cpu=64,mem=256G,gres/gpu=4");
+        let used = tres("cpu=16,mem=300G,node=1");
+
+        // Subtracting stops at zero and ignores what `total` does not list.
+        let free = total.clone() - &used;
+        assert_eq!((free.cpu(), free.mem(), free.gres("gpu")), (48, 0, 4));
+        let sum = free + &used;
+        assert_eq!(sum.to_string(), "cpu=64,mem=300G,gres/gpu=4,node=1");
+
+        assert!(tres("cpu=8,gres/gpu=1").fits_within(&total));
+        assert!(!used.fits_within(&total));
+    }
+
+    #[test]
+    fn equality_ignores_order_and_zeros() {
+        assert_eq!(tres("cpu=1,mem=2G"), tres("mem=2048M,cpu=1"));
+        assert_eq!(tres("cpu=1,node=0"), tres("cpu=1"));
+        assert_eq!(tres(""), tres("gres/gpu=0"));
+        assert_ne!(tres("cpu=1"), tres("cpu=1,node=1"));
+        assert_ne!(tres("gres/gpu=1"), tres("gres/gpu:a100=1"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Sending requests to a slurmrestd listening on a unix socket.
+use crate::Result;
+use hyper::{body::Bytes, client::conn, header::HOST, http};
+use reqwest::{header::HeaderMap, Request, Response};
+use std::{future::Future, io, path::PathBuf, time::Duration};
+use tokio::net::UnixStream;
+
+// A unix socket slurmrestd listens on, e.g. `/run/slurmrestd.sock`.
+// Every request opens its own connection, which is cheap on a local
+// socket and keeps a restarted slurmrestd from breaking the client.
+pub(crate) struct UnixSocket {
+    pub(crate) path: PathBuf,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) connect_timeout: Option<Duration>,
+    // Sent with every request, like `reqwest::Client`'s default headers.
+    pub(crate) headers: HeaderMap,
+}
+
+impl UnixSocket {
+    pub(crate) async fn execute(&self, request: Request) -> Result<Response> {
+        let stream = with_timeout(self.connect_timeout, UnixStream::connect(&self.path)).await?;
+        Ok(with_timeout(self.timeout, self.send(stream, request)).await?)
+    }
+
+    async fn send(&self, stream: UnixStream, request: Request) -> io::Result<Response> {
+        let (mut sender, connection) = conn::handshake(stream).await.map_err(io::Error::other)?;
+        tokio::spawn(connection);
+
+        // slurmrestd only looks at the path, but HTTP/1.1 needs a host.
+        let uri = &request.url()[url::Position::BeforePath..];
+        let mut builder = http::Request::builder()
+            .method(request.method().clone())
+            .uri(uri)
+            .header(HOST, "localhost");
+        let headers = builder.headers_mut().expect("request builder is valid");
+        for (name, value) in &self.headers {
+            headers.insert(name, value.clone());
+        }
+        for (name, value) in request.headers() {
+            headers.insert(name, value.clone());
+        }
+        let body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(Bytes::copy_from_slice)
+            .unwrap_or_default();
+        let request = builder.body(body.into()).map_err(io::Error::other)?;
+
+        let response = sender
+            .send_request(request)
+            .await
+            .map_err(io::Error::other)?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(io::Error::other)?;
+        Ok(Response::from(http::Response::from_parts(parts, body)))
+    }
+}
+
+async fn with_timeout<T, F>(timeout: Option<Duration>, future: F) -> io::Result<T>
+where
+    F: Future<Output = io::Result<T>>,
+{
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "unix socket timed out"))?,
+        None => future.await,
+    }
+}
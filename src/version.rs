@@ -0,0 +1,76 @@
+//! Versions of the Slurm REST API this crate can talk to.
+use crate::SlurmError;
+use std::{fmt, str::FromStr};
+
+/// Value Slurm uses for "no limit" in fields that older API versions
+/// return as plain numbers. Newer versions send `{"infinite": true}`
+/// instead, which is normalized to this value.
+pub const INFINITE: i64 = u32::MAX as i64;
+
+/// [`INFINITE`] for 16 bit fields, such as CPU and task counts.
+pub const INFINITE16: i64 = u16::MAX as i64;
+
+/// [`INFINITE`] for 64 bit fields, such as memory sizes and timestamps.
+///
+/// Slurm uses `u64::MAX`, which does not fit in an `i64`, so the largest
+/// `i64` stands in for it. Requests are translated back to Slurm's value.
+pub const INFINITE64: i64 = i64::MAX;
+
+/// A version of the slurmrestd `slurm` and `slurmdb` plugins.
+///
+/// Responses from every version are normalized into the same types, so
+/// switching versions does not change the shape of what endpoint methods
+/// return.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ApiVersion {
+    /// Slurm 21.08 and 22.05
+    V0_0_37,
+    /// Slurm 22.05 and 23.02
+    #[default]
+    V0_0_38,
+    /// Slurm 23.02 and 23.11
+    V0_0_39,
+    /// Slurm 23.11
+    V0_0_40,
+    /// Slurm 24.05 and 24.11
+    V0_0_41,
+}
+
+impl ApiVersion {
+    /// Every version this crate understands, oldest first.
+    pub const ALL: [ApiVersion; 5] = [
+        ApiVersion::V0_0_37,
+        ApiVersion::V0_0_38,
+        ApiVersion::V0_0_39,
+        ApiVersion::V0_0_40,
+        ApiVersion::V0_0_41,
+    ];
+
+    /// The version as it appears in URLs, e.g. `v0.0.38`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V0_0_37 => "v0.0.37",
+            ApiVersion::V0_0_38 => "v0.0.38",
+            ApiVersion::V0_0_39 => "v0.0.39",
+            ApiVersion::V0_0_40 => "v0.0.40",
+            ApiVersion::V0_0_41 => "v0.0.41",
+        }
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = SlurmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ApiVersion::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s || &v.as_str()[1..] == s)
+            .ok_or_else(|| SlurmError::Config(format!("unsupported API version '{s}'")))
+    }
+}